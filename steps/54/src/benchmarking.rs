@@ -0,0 +1,541 @@
+// Benchmarks for the Kitties Pallet.
+//
+// Each benchmark sets up the worst case for its call, for example an owner and receiver who are at
+// the limit of kitties they can own, so that the resulting weights are always safe.
+//
+// Run the benchmarks as tests with:
+// `cargo test --features runtime-benchmarks`
+//
+// Learn more about benchmarking:
+// https://paritytech.github.io/polkadot-sdk/master/frame_benchmarking/v2/index.html
+
+use super::*;
+use alloc::vec::Vec;
+use frame::arithmetic::Saturating;
+use frame::arithmetic::Zero;
+use frame::deps::frame_benchmarking::v2::*;
+use frame::primitives::BlakeTwo256;
+use frame::traits::fungible::Inspect;
+use frame::traits::fungible::Mutate;
+use frame::traits::Hash;
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+// Creates a unique kitty id from a seed. Hashing the seed gives each kitty very different DNA, so
+// that breeding them never reproduces a parent.
+fn kitty_id(seed: u32) -> [u8; 32] {
+	BlakeTwo256::hash_of(&seed).into()
+}
+
+// Mints `n` kitties to `owner`, using the seeds starting at `first_seed`. The owner is funded so
+// that they can pay the deposits.
+fn mint_kitties<T: Config>(owner: &T::AccountId, first_seed: u32, n: u32) -> Vec<[u8; 32]> {
+	fund::<T>(owner);
+	(first_seed..first_seed + n)
+		.map(|seed| {
+			let id = kitty_id(seed);
+			Pallet::<T>::mint(owner.clone(), id, None).expect("owner is below the limit; qed");
+			id
+		})
+		.collect()
+}
+
+// Gives `who` plenty of funds, returning an amount they can comfortably spend.
+fn fund<T: Config>(who: &T::AccountId) -> BalanceOf<T> {
+	let ed = T::NativeBalance::minimum_balance();
+	T::NativeBalance::set_balance(who, ed.saturating_mul(1_000_000u32.into()));
+	ed.saturating_mul(1_000u32.into())
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn create_kitty() -> Result<(), BenchmarkError> {
+		let max_owned = T::MaxKittiesOwned::get();
+		let caller: T::AccountId = whitelisted_caller();
+		mint_kitties::<T>(&caller, 0, max_owned - 1);
+		// Paying for the kitty is the more expensive path.
+		MintPrice::<T>::put(T::NativeBalance::minimum_balance());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert_eq!(CountForKittiesOwned::<T>::get(&caller), max_owned);
+
+		Ok(())
+	}
+
+	// The owner and the receiver both already own `n` kitties. Transfers do not iterate over the
+	// kitties of either account, so the weight should have no slope in `n`.
+	#[benchmark]
+	fn transfer(
+		n: Linear<0, { T::MaxKittiesOwned::get().saturating_sub(1) }>,
+	) -> Result<(), BenchmarkError> {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let caller: T::AccountId = whitelisted_caller();
+		let receiver: T::AccountId = account("receiver", 0, SEED);
+		let kitty_id = *mint_kitties::<T>(&owner, 0, n + 1).last().unwrap();
+		mint_kitties::<T>(&receiver, n + 1, n);
+		// An operator acting for the owner is the more expensive path.
+		Pallet::<T>::do_set_approval_for_all(owner, caller.clone(), true)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), receiver.clone(), kitty_id);
+
+		assert_eq!(Kitties::<T>::get(kitty_id).unwrap().owner, receiver);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_price() -> Result<(), BenchmarkError> {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let caller: T::AccountId = whitelisted_caller();
+		let kitty_id = mint_kitties::<T>(&owner, 0, 1)[0];
+		let price = T::NativeBalance::minimum_balance();
+		Pallet::<T>::do_set_approval_for_all(owner, caller.clone(), true)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), kitty_id, Some(price));
+
+		assert_eq!(Kitties::<T>::get(kitty_id).unwrap().price, Some(price));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn buy_kitty() -> Result<(), BenchmarkError> {
+		let max_owned = T::MaxKittiesOwned::get();
+		let creator: T::AccountId = account("creator", 0, SEED);
+		let seller: T::AccountId = account("seller", 0, SEED);
+		let buyer: T::AccountId = whitelisted_caller();
+		fund::<T>(&creator);
+		fund::<T>(&T::FeeDestination::get());
+		let price = fund::<T>(&seller);
+		fund::<T>(&buyer);
+		// The kitty pays a royalty to its creator, and the seller and buyer own as many kitties as
+		// they can.
+		let kitty_id = mint_kitties::<T>(&creator, 0, 1)[0];
+		mint_kitties::<T>(&seller, 1, max_owned - 1);
+		Pallet::<T>::do_transfer(creator, seller.clone(), kitty_id)?;
+		mint_kitties::<T>(&buyer, max_owned, max_owned - 1);
+		// Listing in a Dutch auction is the more expensive way to compute the price.
+		Pallet::<T>::do_start_dutch_auction(
+			seller,
+			kitty_id,
+			price,
+			price,
+			T::MaxAuctionDuration::get(),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(buyer.clone()), kitty_id, price);
+
+		assert_eq!(Kitties::<T>::get(kitty_id).unwrap().owner, buyer);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn breed() -> Result<(), BenchmarkError> {
+		let max_owned = T::MaxKittiesOwned::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let kitties = mint_kitties::<T>(&caller, 0, max_owned - 2);
+		let (parent_1, parent_2) = (kitties[0], kitties[1]);
+		// Both parents have bred before, so their cooldowns are read and updated.
+		Pallet::<T>::do_breed(caller.clone(), parent_1, parent_2)?;
+		let ready =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::MaxBreedingCooldown::get());
+		frame_system::Pallet::<T>::set_block_number(ready);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), parent_1, parent_2);
+
+		assert_eq!(CountForKittiesOwned::<T>::get(&caller), max_owned);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn start_auction() -> Result<(), BenchmarkError> {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let caller: T::AccountId = whitelisted_caller();
+		let kitty_id = mint_kitties::<T>(&owner, 0, 1)[0];
+		let duration = T::MaxAuctionDuration::get();
+		// An operator acting for the owner is the more expensive path.
+		Pallet::<T>::do_set_approval_for_all(owner, caller.clone(), true)?;
+		// Fill up the auctions ending in the same block, leaving room for one more.
+		for i in 1..T::MaxAuctionsPerBlock::get() {
+			let seller: T::AccountId = account("seller", i, SEED);
+			let other = mint_kitties::<T>(&seller, i, 1)[0];
+			Pallet::<T>::do_start_auction(seller, other, Zero::zero(), duration)?;
+		}
+		let reserve = T::NativeBalance::minimum_balance();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), kitty_id, reserve, duration);
+
+		assert!(Auctions::<T>::contains_key(kitty_id));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn bid() -> Result<(), BenchmarkError> {
+		let seller: T::AccountId = account("seller", 0, SEED);
+		let previous_bidder: T::AccountId = account("bidder", 0, SEED);
+		let bidder: T::AccountId = whitelisted_caller();
+		let amount = fund::<T>(&previous_bidder);
+		fund::<T>(&bidder);
+		let kitty_id = mint_kitties::<T>(&seller, 0, 1)[0];
+		Pallet::<T>::do_start_auction(
+			seller,
+			kitty_id,
+			Zero::zero(),
+			T::MaxAuctionDuration::get(),
+		)?;
+		// The previous highest bid must be refunded.
+		Pallet::<T>::do_bid(previous_bidder, kitty_id, amount)?;
+		let amount = amount.saturating_add(T::NativeBalance::minimum_balance());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(bidder.clone()), kitty_id, amount);
+
+		let auction = Auctions::<T>::get(kitty_id).unwrap();
+		assert!(auction.highest_bid.is_some_and(|(highest, _)| highest == bidder));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn start_dutch_auction() -> Result<(), BenchmarkError> {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let caller: T::AccountId = whitelisted_caller();
+		let kitty_id = mint_kitties::<T>(&owner, 0, 1)[0];
+		Pallet::<T>::do_set_approval_for_all(owner, caller.clone(), true)?;
+		let end_price = T::NativeBalance::minimum_balance();
+		let start_price = end_price.saturating_mul(10u32.into());
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			kitty_id,
+			start_price,
+			end_price,
+			T::MaxAuctionDuration::get(),
+		);
+
+		assert!(DutchAuctions::<T>::contains_key(kitty_id));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn make_offer() -> Result<(), BenchmarkError> {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let bidder: T::AccountId = whitelisted_caller();
+		let amount = fund::<T>(&bidder);
+		let kitty_id = mint_kitties::<T>(&owner, 0, 1)[0];
+		let now = frame_system::Pallet::<T>::block_number();
+		// The kitty has the maximum number of offers, and the bidder outbids the lowest one.
+		for i in 0..T::MaxOffersPerKitty::get() {
+			let other: T::AccountId = account("bidder", i, SEED);
+			let other_amount = fund::<T>(&other);
+			let expires_at = now.saturating_add(i.saturating_add(1).into());
+			Pallet::<T>::do_make_offer(other, kitty_id, other_amount, expires_at)?;
+		}
+		let expires_at = now.saturating_add(T::MaxOffersPerKitty::get().into());
+		let amount = amount.saturating_add(T::NativeBalance::minimum_balance());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(bidder.clone()), kitty_id, amount, expires_at);
+
+		assert_eq!(Offers::<T>::get(kitty_id).len() as u32, T::MaxOffersPerKitty::get());
+		assert!(Offers::<T>::get(kitty_id).iter().any(|offer| offer.bidder == bidder));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn withdraw_offer() -> Result<(), BenchmarkError> {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let bidder: T::AccountId = whitelisted_caller();
+		let kitty_id = mint_kitties::<T>(&owner, 0, 1)[0];
+		let now = frame_system::Pallet::<T>::block_number();
+		// The kitty has the maximum number of offers, and the offer is the last one found.
+		for i in 1..T::MaxOffersPerKitty::get() {
+			let other: T::AccountId = account("bidder", i, SEED);
+			let other_amount = fund::<T>(&other);
+			let expires_at = now.saturating_add(i.into());
+			Pallet::<T>::do_make_offer(other, kitty_id, other_amount, expires_at)?;
+		}
+		let amount = fund::<T>(&bidder);
+		let expires_at = now.saturating_add(T::MaxOffersPerKitty::get().into());
+		Pallet::<T>::do_make_offer(bidder.clone(), kitty_id, amount, expires_at)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(bidder.clone()), kitty_id);
+
+		assert!(Offers::<T>::get(kitty_id).iter().all(|offer| offer.bidder != bidder));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn accept_offer() -> Result<(), BenchmarkError> {
+		let max_owned = T::MaxKittiesOwned::get();
+		let owner: T::AccountId = whitelisted_caller();
+		let bidder: T::AccountId = account("bidder", 0, SEED);
+		fund::<T>(&owner);
+		// The owner and the bidder own as many kitties as they can.
+		let kitty_id = *mint_kitties::<T>(&owner, 0, max_owned).last().unwrap();
+		mint_kitties::<T>(&bidder, max_owned, max_owned - 1);
+		let now = frame_system::Pallet::<T>::block_number();
+		// The kitty has the maximum number of offers, and the offer is the last one found.
+		for i in 1..T::MaxOffersPerKitty::get() {
+			let other: T::AccountId = account("bidder", i, SEED);
+			let other_amount = fund::<T>(&other);
+			let expires_at = now.saturating_add(i.into());
+			Pallet::<T>::do_make_offer(other, kitty_id, other_amount, expires_at)?;
+		}
+		let amount = fund::<T>(&bidder);
+		let expires_at = now.saturating_add(T::MaxOffersPerKitty::get().into());
+		Pallet::<T>::do_make_offer(bidder.clone(), kitty_id, amount, expires_at)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(owner), kitty_id, bidder.clone());
+
+		assert_eq!(Kitties::<T>::get(kitty_id).unwrap().owner, bidder);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn burn() -> Result<(), BenchmarkError> {
+		let owner: T::AccountId = whitelisted_caller();
+		let kitties = mint_kitties::<T>(&owner, 0, 2);
+		let kitty_id = kitties[0];
+		// The kitty has bred, is listed, and has the maximum number of offers to refund.
+		Pallet::<T>::do_breed(owner.clone(), kitties[0], kitties[1])?;
+		Pallet::<T>::do_start_dutch_auction(
+			owner.clone(),
+			kitty_id,
+			T::NativeBalance::minimum_balance(),
+			T::NativeBalance::minimum_balance(),
+			T::MaxAuctionDuration::get(),
+		)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		for i in 0..T::MaxOffersPerKitty::get() {
+			let bidder: T::AccountId = account("bidder", i, SEED);
+			let amount = fund::<T>(&bidder);
+			let expires_at = now.saturating_add(i.saturating_add(1).into());
+			Pallet::<T>::do_make_offer(bidder, kitty_id, amount, expires_at)?;
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(owner), kitty_id);
+
+		assert!(!Kitties::<T>::contains_key(kitty_id));
+		assert!(BurnedKitties::<T>::contains_key(kitty_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn approve_transfer() -> Result<(), BenchmarkError> {
+		let owner: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let kitty_id = mint_kitties::<T>(&owner, 0, 1)[0];
+		let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(10u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(owner), kitty_id, delegate, Some(expires_at));
+
+		assert!(Approvals::<T>::contains_key(kitty_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_approval() -> Result<(), BenchmarkError> {
+		let owner: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let kitty_id = mint_kitties::<T>(&owner, 0, 1)[0];
+		Pallet::<T>::do_approve_transfer(owner.clone(), kitty_id, delegate, None)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(owner), kitty_id);
+
+		assert!(!Approvals::<T>::contains_key(kitty_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_from() -> Result<(), BenchmarkError> {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let delegate: T::AccountId = whitelisted_caller();
+		let receiver: T::AccountId = account("receiver", 0, SEED);
+		let kitty_id = mint_kitties::<T>(&owner, 0, 1)[0];
+		fund::<T>(&receiver);
+		// Checking the expiry is the more expensive path.
+		let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(10u32.into());
+		Pallet::<T>::do_approve_transfer(owner, kitty_id, delegate.clone(), Some(expires_at))?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(delegate), kitty_id, receiver.clone());
+
+		assert_eq!(Kitties::<T>::get(kitty_id).unwrap().owner, receiver);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_approval_for_all() -> Result<(), BenchmarkError> {
+		let owner: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, SEED);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(owner.clone()), operator.clone(), true);
+
+		assert!(OperatorApprovals::<T>::contains_key(&owner, &operator));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_mint_price() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let price = T::NativeBalance::minimum_balance();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, price);
+
+		assert_eq!(MintPrice::<T>::get(), price);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_mint() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let max_owned = T::MaxKittiesOwned::get();
+		let owner: T::AccountId = account("owner", 0, SEED);
+		mint_kitties::<T>(&owner, 0, max_owned - 1);
+		let dna = kitty_id(max_owned);
+		// The deposit of the kitty is paid by the `MintBeneficiary`.
+		fund::<T>(&T::MintBeneficiary::get());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, owner.clone(), dna);
+
+		assert_eq!(Kitties::<T>::get(dna).unwrap().owner, owner);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_transfer() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let max_owned = T::MaxKittiesOwned::get();
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let receiver: T::AccountId = account("receiver", 0, SEED);
+		let kitty_id = *mint_kitties::<T>(&owner, 0, max_owned).last().unwrap();
+		mint_kitties::<T>(&receiver, max_owned, max_owned - 1);
+		// The kitty is in an auction, which is cancelled and its highest bid refunded.
+		let bidder: T::AccountId = account("bidder", 0, SEED);
+		let amount = fund::<T>(&bidder);
+		Pallet::<T>::do_start_auction(owner, kitty_id, Zero::zero(), T::MaxAuctionDuration::get())?;
+		Pallet::<T>::do_bid(bidder, kitty_id, amount)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, kitty_id, receiver.clone());
+
+		assert_eq!(Kitties::<T>::get(kitty_id).unwrap().owner, receiver);
+		assert!(!Auctions::<T>::contains_key(kitty_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_set_price() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let kitty_id = mint_kitties::<T>(&owner, 0, 1)[0];
+		let price = T::NativeBalance::minimum_balance();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, kitty_id, Some(price));
+
+		assert_eq!(Kitties::<T>::get(kitty_id).unwrap().price, Some(price));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_paused() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let paused = PauseFlags { mint: true, transfer: true, sale: true };
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, paused);
+
+		assert_eq!(Paused::<T>::get(), paused);
+		Ok(())
+	}
+
+	// Every auction has a winning bid, and pays a royalty to a creator who is not the seller.
+	#[benchmark]
+	fn settle_auctions(
+		n: Linear<0, { T::MaxAuctionsPerBlock::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let creator: T::AccountId = account("creator", 0, SEED);
+		let duration = T::MaxAuctionDuration::get();
+		let end = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+		for i in 0..n {
+			let seller: T::AccountId = account("seller", i, SEED);
+			let bidder: T::AccountId = account("bidder", i, SEED);
+			fund::<T>(&seller);
+			let kitty_id = mint_kitties::<T>(&creator, i, 1)[0];
+			Pallet::<T>::do_transfer(creator.clone(), seller.clone(), kitty_id)?;
+			Pallet::<T>::do_start_auction(seller, kitty_id, Zero::zero(), duration)?;
+			let amount = fund::<T>(&bidder);
+			Pallet::<T>::do_bid(bidder, kitty_id, amount)?;
+		}
+
+		#[block]
+		{
+			Pallet::<T>::settle_auctions(end);
+		}
+
+		assert!(!AuctionsEndingAt::<T>::contains_key(end));
+		for i in 0..n {
+			let bidder: T::AccountId = account("bidder", i, SEED);
+			assert_eq!(Kitties::<T>::get(kitty_id(i)).unwrap().owner, bidder);
+		}
+		Ok(())
+	}
+
+	// Every offer is for a different kitty, and refunds its bidder.
+	#[benchmark]
+	fn expire_offers(n: Linear<0, { T::MaxOffersPerBlock::get() }>) -> Result<(), BenchmarkError> {
+		let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(1u32.into());
+		for i in 0..n {
+			let owner: T::AccountId = account("owner", i, SEED);
+			let bidder: T::AccountId = account("bidder", i, SEED);
+			let kitty_id = mint_kitties::<T>(&owner, i, 1)[0];
+			let amount = fund::<T>(&bidder);
+			Pallet::<T>::do_make_offer(bidder, kitty_id, amount, expires_at)?;
+		}
+
+		#[block]
+		{
+			Pallet::<T>::expire_offers(expires_at);
+		}
+
+		assert!(!OffersExpiringAt::<T>::contains_key(expires_at));
+		assert_eq!(Offers::<T>::iter().count(), 0);
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::TestRuntime);
+}
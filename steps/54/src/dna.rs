@@ -0,0 +1,223 @@
+// The genome of a kitty.
+//
+// A kitty's DNA is 32 bytes, and each of the first few bytes is a gene which decides one of the
+// kitty's traits. The remaining bytes are reserved for future traits, but still contribute to the
+// uniqueness of each kitty.
+//
+// `Dna` encodes exactly like `[u8; 32]`, so it can be used anywhere raw DNA was stored before.
+
+use frame::prelude::*;
+use scale_info::build::Fields;
+use scale_info::Path;
+use scale_info::Type;
+
+// The byte position of each gene in the DNA.
+pub const FUR_COLOUR_GENE: usize = 0;
+pub const EYE_COLOUR_GENE: usize = 1;
+pub const EYE_SHAPE_GENE: usize = 2;
+pub const PATTERN_GENE: usize = 3;
+pub const RARITY_GENE: usize = 4;
+
+/// The number of bytes which do not (yet) encode a trait.
+pub const RESERVED_GENES: usize = 32 - RARITY_GENE - 1;
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, RuntimeDebug)]
+pub struct Dna([u8; 32]);
+
+impl Dna {
+	pub const fn new(bytes: [u8; 32]) -> Self {
+		Self(bytes)
+	}
+
+	pub fn as_bytes(&self) -> &[u8; 32] {
+		&self.0
+	}
+
+	pub fn fur_colour(&self) -> FurColour {
+		FurColour::from_gene(self.0[FUR_COLOUR_GENE])
+	}
+
+	pub fn eye_colour(&self) -> EyeColour {
+		EyeColour::from_gene(self.0[EYE_COLOUR_GENE])
+	}
+
+	pub fn eye_shape(&self) -> EyeShape {
+		EyeShape::from_gene(self.0[EYE_SHAPE_GENE])
+	}
+
+	pub fn pattern(&self) -> Pattern {
+		Pattern::from_gene(self.0[PATTERN_GENE])
+	}
+
+	pub fn rarity(&self) -> Rarity {
+		Rarity::from_gene(self.0[RARITY_GENE])
+	}
+}
+
+impl From<[u8; 32]> for Dna {
+	fn from(bytes: [u8; 32]) -> Self {
+		Self(bytes)
+	}
+}
+
+impl From<Dna> for [u8; 32] {
+	fn from(dna: Dna) -> Self {
+		dna.0
+	}
+}
+
+impl PartialEq<[u8; 32]> for Dna {
+	fn eq(&self, other: &[u8; 32]) -> bool {
+		&self.0 == other
+	}
+}
+
+// We describe the genome gene by gene, rather than as an opaque byte array, so that the metadata
+// tells clients how to read the DNA. This matches the encoding, since every gene is a single byte.
+impl TypeInfo for Dna {
+	type Identity = Self;
+
+	fn type_info() -> Type {
+		Type::builder()
+			.path(Path::new("Dna", module_path!()))
+			.docs(&["The genome of a kitty. Each gene is a single byte of the DNA."])
+			.composite(
+				Fields::named()
+					.field(|f| f.ty::<u8>().name("fur_colour").type_name("FurColour"))
+					.field(|f| f.ty::<u8>().name("eye_colour").type_name("EyeColour"))
+					.field(|f| f.ty::<u8>().name("eye_shape").type_name("EyeShape"))
+					.field(|f| f.ty::<u8>().name("pattern").type_name("Pattern"))
+					.field(|f| f.ty::<u8>().name("rarity").type_name("Rarity"))
+					.field(|f| f.ty::<[u8; RESERVED_GENES]>().name("reserved")),
+			)
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum FurColour {
+	Black,
+	White,
+	Ginger,
+	Grey,
+	Brown,
+	Cream,
+	Blue,
+	Calico,
+}
+
+impl FurColour {
+	const ALL: [Self; 8] = [
+		Self::Black,
+		Self::White,
+		Self::Ginger,
+		Self::Grey,
+		Self::Brown,
+		Self::Cream,
+		Self::Blue,
+		Self::Calico,
+	];
+
+	pub fn from_gene(gene: u8) -> Self {
+		Self::ALL[gene as usize % Self::ALL.len()]
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum EyeColour {
+	Green,
+	Blue,
+	Amber,
+	Copper,
+}
+
+impl EyeColour {
+	const ALL: [Self; 4] = [Self::Green, Self::Blue, Self::Amber, Self::Copper];
+
+	pub fn from_gene(gene: u8) -> Self {
+		Self::ALL[gene as usize % Self::ALL.len()]
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum EyeShape {
+	Round,
+	Almond,
+	Sleepy,
+	Wide,
+}
+
+impl EyeShape {
+	const ALL: [Self; 4] = [Self::Round, Self::Almond, Self::Sleepy, Self::Wide];
+
+	pub fn from_gene(gene: u8) -> Self {
+		Self::ALL[gene as usize % Self::ALL.len()]
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum Pattern {
+	Solid,
+	Tabby,
+	Tuxedo,
+	Tortoiseshell,
+	Spotted,
+	Bicolour,
+	Pointed,
+	Striped,
+}
+
+impl Pattern {
+	const ALL: [Self; 8] = [
+		Self::Solid,
+		Self::Tabby,
+		Self::Tuxedo,
+		Self::Tortoiseshell,
+		Self::Spotted,
+		Self::Bicolour,
+		Self::Pointed,
+		Self::Striped,
+	];
+
+	pub fn from_gene(gene: u8) -> Self {
+		Self::ALL[gene as usize % Self::ALL.len()]
+	}
+}
+
+/// Rarity is not evenly distributed: higher tiers are decided by a smaller range of gene values.
+#[derive(
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	RuntimeDebug,
+)]
+pub enum Rarity {
+	/// Gene `0..=159`.
+	Common,
+	/// Gene `160..=219`.
+	Uncommon,
+	/// Gene `220..=249`.
+	Rare,
+	/// Gene `250..=254`.
+	Epic,
+	/// Gene `255`.
+	Legendary,
+}
+
+impl Rarity {
+	pub fn from_gene(gene: u8) -> Self {
+		match gene {
+			0..=159 => Self::Common,
+			160..=219 => Self::Uncommon,
+			220..=249 => Self::Rare,
+			250..=254 => Self::Epic,
+			255 => Self::Legendary,
+		}
+	}
+}
@@ -1,8 +1,25 @@
 use super::*;
+#[cfg(any(feature = "try-runtime", test))]
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use frame::arithmetic::One;
+use frame::arithmetic::Perbill;
+use frame::arithmetic::Saturating;
+use frame::arithmetic::Zero;
+#[cfg(any(feature = "try-runtime", test))]
+use frame::deps::sp_runtime::TryRuntimeError;
 use frame::prelude::*;
 use frame::primitives::BlakeTwo256;
+#[cfg(any(feature = "try-runtime", test))]
+use frame::traits::fungible::InspectHold;
+use frame::traits::tokens::DepositConsequence;
+use frame::traits::tokens::Fortitude;
+use frame::traits::tokens::Precision;
 use frame::traits::tokens::Preservation;
+use frame::traits::tokens::Provenance;
+use frame::traits::tokens::Restriction;
 use frame::traits::Hash;
+use runtime_api::KittyInfo;
 
 // Learn about internal functions.
 impl<T: Config> Pallet<T> {
@@ -20,73 +37,868 @@ impl<T: Config> Pallet<T> {
 		BlakeTwo256::hash_of(&unique_payload).into()
 	}
 
-	pub fn mint(owner: T::AccountId, dna: [u8; 32]) -> DispatchResult {
-		let kitty = Kitty { dna, owner: owner.clone(), price: None };
+	// Mixes the DNA of two parents. Each bit of the child comes from one of the parents, selected
+	// using the same entropy as `gen_dna`.
+	pub fn breed_dna(dna_1: &[u8; 32], dna_2: &[u8; 32]) -> [u8; 32] {
+		let selector = Self::gen_dna();
+		let mut child = [0u8; 32];
+		for i in 0..child.len() {
+			child[i] = (dna_1[i] & selector[i]) | (dna_2[i] & !selector[i]);
+		}
+		child
+	}
+
+	// Returns how long a kitty must rest after breeding, given how many times it has bred before.
+	// The cooldown starts at `BreedingCooldownBase` and is multiplied by `BreedingCooldownFactor`
+	// for every previous breeding, up to `MaxBreedingCooldown`.
+	pub fn breeding_cooldown(times_bred: u32) -> BlockNumberFor<T> {
+		let fatigue: BlockNumberFor<T> =
+			T::BreedingCooldownFactor::get().saturating_pow(times_bred).into();
+		T::BreedingCooldownBase::get()
+			.saturating_mul(fatigue)
+			.min(T::MaxBreedingCooldown::get())
+	}
+
+	pub fn mint(
+		owner: T::AccountId,
+		dna: [u8; 32],
+		parents: Option<([u8; 32], [u8; 32])>,
+	) -> DispatchResult {
 		// Check if the kitty does not already exist in our storage map
 		ensure!(!Kitties::<T>::contains_key(dna), Error::<T>::DuplicateKitty);
+		ensure!(!BurnedKitties::<T>::contains_key(dna), Error::<T>::KittyBurned);
+
+		// Children are always one generation above their oldest parent.
+		let generation = match parents {
+			Some((parent_1, parent_2)) => {
+				let kitty_1 = Kitties::<T>::get(parent_1).ok_or(Error::<T>::NoKitty)?;
+				let kitty_2 = Kitties::<T>::get(parent_2).ok_or(Error::<T>::NoKitty)?;
+				kitty_1.generation.max(kitty_2.generation).saturating_add(1)
+			},
+			None => 0,
+		};
+		let born_at = frame_system::Pallet::<T>::block_number();
+		let kitty = Kitty {
+			dna: dna.into(),
+			owner: owner.clone(),
+			price: None,
+			parents,
+			generation,
+			born_at,
+		};
 
 		let current_count: u32 = CountForKitties::<T>::get();
 		let new_count = current_count.checked_add(1).ok_or(Error::<T>::TooManyKitties)?;
 
-		KittiesOwned::<T>::try_append(&owner, dna).map_err(|_| Error::<T>::TooManyOwned)?;
+		let owned = CountForKittiesOwned::<T>::get(&owner);
+		ensure!(owned < T::MaxKittiesOwned::get(), Error::<T>::TooManyOwned);
+
+		let deposit = T::KittyDeposit::get();
+		if !deposit.is_zero() {
+			T::NativeBalance::hold(&HoldReason::KittyStorage.into(), &owner, deposit)?;
+			KittyDeposits::<T>::insert(dna, deposit);
+		}
+		KittiesOwned::<T>::insert(&owner, dna, ());
+		CountForKittiesOwned::<T>::insert(&owner, owned.saturating_add(1));
 		Kitties::<T>::insert(dna, kitty);
+		Creators::<T>::insert(dna, &owner);
 		CountForKitties::<T>::set(new_count);
 
 		Self::deposit_event(Event::<T>::Created { owner });
 		Ok(())
 	}
 
-	pub fn do_transfer(from: T::AccountId, to: T::AccountId, kitty_id: [u8; 32]) -> DispatchResult {
-		ensure!(from != to, Error::<T>::TransferToSelf);
+	// Returns whether `who` can manage the kitties of `owner`, either as the owner themselves or as
+	// one of their operators.
+	pub fn is_owner_or_operator(owner: &T::AccountId, who: &T::AccountId) -> bool {
+		owner == who || OperatorApprovals::<T>::contains_key(owner, who)
+	}
+
+	// Charges the minter the current mint price, and gives them a new kitty with random DNA.
+	pub fn do_create_kitty(minter: T::AccountId) -> DispatchResult {
+		let price = MintPrice::<T>::get();
+		if !price.is_zero() {
+			let beneficiary = T::MintBeneficiary::get();
+			T::NativeBalance::transfer(&minter, &beneficiary, price, Preservation::Preserve)?;
+		}
+		let dna = Self::gen_dna();
+		Self::mint(minter, dna, None)
+	}
+
+	pub fn do_set_mint_price(price: BalanceOf<T>) {
+		MintPrice::<T>::put(price);
+		Self::deposit_event(Event::<T>::MintPriceUpdated { price });
+	}
+
+	pub fn do_set_paused(paused: PauseFlags) {
+		Paused::<T>::put(paused);
+		Self::deposit_event(Event::<T>::PausedUpdated { paused });
+	}
+
+	// The forced functions skip the checks on the caller, but otherwise follow the same rules as
+	// their regular versions. Each emits an extra event after the regular one, so that forced
+	// actions can be told apart.
+	//
+	// The owner of a forced kitty did not ask for it, so the `MintBeneficiary` pays its deposit.
+	// The deposit is sent to the owner and held there, so it follows the kitty like any other.
+	pub fn do_force_mint(owner: T::AccountId, dna: [u8; 32]) -> DispatchResult {
+		let deposit = T::KittyDeposit::get();
+		if !deposit.is_zero() {
+			let payer = T::MintBeneficiary::get();
+			T::NativeBalance::transfer(&payer, &owner, deposit, Preservation::Preserve)?;
+		}
+		Self::mint(owner.clone(), dna, None)?;
+		Self::deposit_event(Event::<T>::ForceMinted { owner, kitty_id: dna });
+		Ok(())
+	}
+
+	// Unlike a regular transfer, a forced transfer is not blocked by an auction, which is cancelled
+	// and its highest bid refunded. A lock still blocks it, since the pallet which placed the lock
+	// relies on the kitty staying put, so the admin must ask the lock owner to unlock it first.
+	pub fn do_force_transfer(kitty_id: [u8; 32], to: T::AccountId) -> DispatchResult {
+		let from = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?.owner;
+		if let Some(auction) = Auctions::<T>::take(kitty_id) {
+			Self::cancel_auction(kitty_id, auction)?;
+		}
+		Self::do_transfer(from.clone(), to.clone(), kitty_id)?;
+		Self::deposit_event(Event::<T>::ForceTransferred { from, to, kitty_id });
+		Ok(())
+	}
+
+	pub fn do_force_set_price(
+		kitty_id: [u8; 32],
+		new_price: Option<BalanceOf<T>>,
+	) -> DispatchResult {
+		let owner = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?.owner;
+		Self::do_set_price(owner.clone(), kitty_id, new_price)?;
+		Self::deposit_event(Event::<T>::ForcePriceSet { owner, kitty_id, new_price });
+		Ok(())
+	}
+
+	// Transfers a kitty on behalf of `caller`, who must be its owner or one of their operators.
+	pub fn do_transfer(
+		caller: T::AccountId,
+		to: T::AccountId,
+		kitty_id: [u8; 32],
+	) -> DispatchResult {
 		let mut kitty = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?;
-		ensure!(kitty.owner == from, Error::<T>::NotOwner);
+		ensure!(Self::is_owner_or_operator(&kitty.owner, &caller), Error::<T>::NotOwner);
+		ensure!(kitty.owner != to, Error::<T>::TransferToSelf);
+		ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+		ensure!(!KittyLocks::<T>::contains_key(kitty_id), Error::<T>::KittyLocked);
+		let from = kitty.owner.clone();
 		kitty.owner = to.clone();
 		kitty.price = None;
+		DutchAuctions::<T>::remove(kitty_id);
+		// An approval is given by the owner, so it does not carry over to the next one.
+		Approvals::<T>::remove(kitty_id);
 
-		let mut to_owned = KittiesOwned::<T>::get(&to);
-		to_owned.try_push(kitty_id).map_err(|_| Error::<T>::TooManyOwned)?;
-		let mut from_owned = KittiesOwned::<T>::get(&from);
-		if let Some(ind) = from_owned.iter().position(|&id| id == kitty_id) {
-			from_owned.swap_remove(ind);
-		} else {
-			return Err(Error::<T>::NoKitty.into())
-		}
+		let to_owned = CountForKittiesOwned::<T>::get(&to);
+		ensure!(to_owned < T::MaxKittiesOwned::get(), Error::<T>::TooManyOwned);
+		let from_owned = CountForKittiesOwned::<T>::get(&from)
+			.checked_sub(1)
+			.ok_or(Error::<T>::NoKitty)?;
 
+		// The deposit for the kitty is now owed by the new owner.
+		if let Some(deposit) = KittyDeposits::<T>::get(kitty_id) {
+			T::NativeBalance::transfer_on_hold(
+				&HoldReason::KittyStorage.into(),
+				&from,
+				&to,
+				deposit,
+				Precision::Exact,
+				Restriction::OnHold,
+				Fortitude::Polite,
+			)?;
+		}
 		Kitties::<T>::insert(kitty_id, kitty);
-		KittiesOwned::<T>::insert(&to, to_owned);
-		KittiesOwned::<T>::insert(&from, from_owned);
+		KittiesOwned::<T>::remove(&from, kitty_id);
+		KittiesOwned::<T>::insert(&to, kitty_id, ());
+		Self::set_count_owned(&from, from_owned);
+		CountForKittiesOwned::<T>::insert(&to, to_owned.saturating_add(1));
 
 		Self::deposit_event(Event::<T>::Transferred { from, to, kitty_id });
 		Ok(())
 	}
 
+	// Records the number of kitties owned by `owner`, removing the entry once they own none.
+	fn set_count_owned(owner: &T::AccountId, count: u32) {
+		if count == 0 {
+			CountForKittiesOwned::<T>::remove(owner);
+		} else {
+			CountForKittiesOwned::<T>::insert(owner, count);
+		}
+	}
+
+	pub fn do_approve_transfer(
+		owner: T::AccountId,
+		kitty_id: [u8; 32],
+		delegate: T::AccountId,
+		expires_at: Option<BlockNumberFor<T>>,
+	) -> DispatchResult {
+		ensure!(owner != delegate, Error::<T>::ApproveSelf);
+		let kitty = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?;
+		ensure!(kitty.owner == owner, Error::<T>::NotOwner);
+		if let Some(expires_at) = expires_at {
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(expires_at > now, Error::<T>::ApprovalExpired);
+		}
+		// A kitty has at most one delegate, so this replaces any previous approval.
+		Approvals::<T>::insert(kitty_id, Approval { delegate: delegate.clone(), expires_at });
+
+		Self::deposit_event(Event::<T>::TransferApproved { owner, kitty_id, delegate, expires_at });
+		Ok(())
+	}
+
+	pub fn do_set_approval_for_all(
+		owner: T::AccountId,
+		operator: T::AccountId,
+		approved: bool,
+	) -> DispatchResult {
+		ensure!(owner != operator, Error::<T>::ApproveSelf);
+		if approved {
+			OperatorApprovals::<T>::insert(&owner, &operator, ());
+		} else {
+			OperatorApprovals::<T>::remove(&owner, &operator);
+		}
+
+		Self::deposit_event(Event::<T>::ApprovalForAllSet { owner, operator, approved });
+		Ok(())
+	}
+
+	pub fn do_cancel_approval(owner: T::AccountId, kitty_id: [u8; 32]) -> DispatchResult {
+		let kitty = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?;
+		ensure!(kitty.owner == owner, Error::<T>::NotOwner);
+		ensure!(Approvals::<T>::contains_key(kitty_id), Error::<T>::NoApproval);
+		Approvals::<T>::remove(kitty_id);
+
+		Self::deposit_event(Event::<T>::ApprovalCancelled { owner, kitty_id });
+		Ok(())
+	}
+
+	pub fn do_transfer_from(
+		delegate: T::AccountId,
+		kitty_id: [u8; 32],
+		to: T::AccountId,
+	) -> DispatchResult {
+		let kitty = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?;
+		let approval = Approvals::<T>::get(kitty_id).ok_or(Error::<T>::NoApproval)?;
+		ensure!(approval.delegate == delegate, Error::<T>::NotDelegate);
+		if let Some(expires_at) = approval.expires_at {
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now < expires_at, Error::<T>::ApprovalExpired);
+		}
+		// The transfer happens on behalf of the owner, and clears the approval.
+		Self::do_transfer(kitty.owner, to, kitty_id)
+	}
+
 	pub fn do_set_price(
 		caller: T::AccountId,
 		kitty_id: [u8; 32],
 		new_price: Option<BalanceOf<T>>,
 	) -> DispatchResult {
 		let mut kitty = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?;
-		ensure!(kitty.owner == caller, Error::<T>::NotOwner);
+		ensure!(Self::is_owner_or_operator(&kitty.owner, &caller), Error::<T>::NotOwner);
+		ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+		ensure!(!KittyLocks::<T>::contains_key(kitty_id), Error::<T>::KittyLocked);
+		let owner = kitty.owner.clone();
 		kitty.price = new_price;
 		Kitties::<T>::insert(kitty_id, kitty);
+		// A fixed price replaces any descending price listing.
+		DutchAuctions::<T>::remove(kitty_id);
 
-		Self::deposit_event(Event::<T>::PriceSet { owner: caller, kitty_id, new_price });
+		Self::deposit_event(Event::<T>::PriceSet { owner, kitty_id, new_price });
 		Ok(())
 	}
 
+	// Returns the price a kitty can be bought for right now, if it is for sale.
+	pub fn current_price(kitty_id: [u8; 32], kitty: &Kitty<T>) -> Option<BalanceOf<T>> {
+		match DutchAuctions::<T>::get(kitty_id) {
+			Some(auction) => {
+				let now = frame_system::Pallet::<T>::block_number();
+				Some(Self::dutch_auction_price(&auction, now))
+			},
+			None => kitty.price,
+		}
+	}
+
+	// Returns the price of a Dutch auction at block `now`. The price decays linearly between the
+	// start and end of the auction, rounding down, and stays at `end_price` once it has ended.
+	pub fn dutch_auction_price(auction: &DutchAuction<T>, now: BlockNumberFor<T>) -> BalanceOf<T> {
+		if now <= auction.start {
+			return auction.start_price
+		}
+		if now >= auction.end {
+			return auction.end_price
+		}
+		let elapsed = now.saturating_sub(auction.start);
+		let duration = auction.end.saturating_sub(auction.start);
+		let decay = auction.start_price.saturating_sub(auction.end_price);
+		auction
+			.start_price
+			.saturating_sub(Perbill::from_rational(elapsed, duration).mul_ceil(decay))
+	}
+
 	pub fn do_buy_kitty(
 		buyer: T::AccountId,
 		kitty_id: [u8; 32],
 		price: BalanceOf<T>,
 	) -> DispatchResult {
 		let kitty = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?;
-		let real_price = kitty.price.ok_or(Error::<T>::NotForSale)?;
+		// A kitty may still be listed from before it was locked.
+		ensure!(!KittyLocks::<T>::contains_key(kitty_id), Error::<T>::KittyLocked);
+		let real_price = Self::current_price(kitty_id, &kitty).ok_or(Error::<T>::NotForSale)?;
 		ensure!(price >= real_price, Error::<T>::MaxPriceTooLow);
 
-		T::NativeBalance::transfer(&buyer, &kitty.owner, real_price, Preservation::Preserve)?;
+		let pay = |destination: &T::AccountId, amount: BalanceOf<T>| {
+			T::NativeBalance::transfer(&buyer, destination, amount, Preservation::Preserve)
+				.map(|_| ())
+		};
+		let (royalty, fee) = Self::pay_for_sale(kitty_id, &kitty.owner, real_price, pay)?;
 		Self::do_transfer(kitty.owner, buyer.clone(), kitty_id)?;
 
-		Self::deposit_event(Event::<T>::Sold { buyer, kitty_id, price: real_price });
+		Self::deposit_event(Event::<T>::Sold { buyer, kitty_id, price: real_price, royalty, fee });
+		Ok(())
+	}
+
+	// Splits the `price` of a kitty sold by `seller`, using `pay` to move the funds of the buyer.
+	// The creator is paid their royalty first, then the marketplace its fee, and the seller
+	// receives the rest. No royalty is paid when the creator is the seller, or when the creator
+	// cannot receive it, for example when their account was reaped and the royalty is below the
+	// existential deposit. The seller keeps that royalty instead, so the sale does not fail.
+	// Returns the royalty and fee paid. Every way of selling a kitty must pay through this
+	// function.
+	fn pay_for_sale(
+		kitty_id: [u8; 32],
+		seller: &T::AccountId,
+		price: BalanceOf<T>,
+		pay: impl Fn(&T::AccountId, BalanceOf<T>) -> DispatchResult,
+	) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		let royalty = match Creators::<T>::get(kitty_id) {
+			Some(creator) if creator != *seller => {
+				let royalty = T::RoyaltyPercent::get().mul_floor(price);
+				let can_receive =
+					T::NativeBalance::can_deposit(&creator, royalty, Provenance::Extant) ==
+						DepositConsequence::Success;
+				if royalty.is_zero() || !can_receive {
+					Zero::zero()
+				} else {
+					pay(&creator, royalty)?;
+					royalty
+				}
+			},
+			_ => Zero::zero(),
+		};
+		let fee = T::MarketplaceFee::get().mul_floor(price);
+		if !fee.is_zero() {
+			pay(&T::FeeDestination::get(), fee)?;
+		}
+		pay(seller, price.saturating_sub(royalty).saturating_sub(fee))?;
+		Ok((royalty, fee))
+	}
+
+	pub fn do_breed(owner: T::AccountId, parent_1: [u8; 32], parent_2: [u8; 32]) -> DispatchResult {
+		ensure!(parent_1 != parent_2, Error::<T>::BreedWithSelf);
+		let kitty_1 = Kitties::<T>::get(parent_1).ok_or(Error::<T>::NoKitty)?;
+		let kitty_2 = Kitties::<T>::get(parent_2).ok_or(Error::<T>::NoKitty)?;
+		ensure!(kitty_1.owner == owner, Error::<T>::NotOwner);
+		ensure!(kitty_2.owner == owner, Error::<T>::NotOwner);
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut status_1 = BreedingCooldowns::<T>::get(parent_1);
+		let mut status_2 = BreedingCooldowns::<T>::get(parent_2);
+		ensure!(status_1.ready_at <= now, Error::<T>::KittyOnCooldown);
+		ensure!(status_2.ready_at <= now, Error::<T>::KittyOnCooldown);
+
+		let child = Self::breed_dna(kitty_1.dna.as_bytes(), kitty_2.dna.as_bytes());
+		Self::mint(owner.clone(), child, Some((parent_1, parent_2)))?;
+
+		for status in [&mut status_1, &mut status_2] {
+			status.ready_at = now.saturating_add(Self::breeding_cooldown(status.times_bred));
+			status.times_bred.saturating_inc();
+		}
+		BreedingCooldowns::<T>::insert(parent_1, status_1);
+		BreedingCooldowns::<T>::insert(parent_2, status_2);
+
+		Self::deposit_event(Event::<T>::Bred { owner, parents: (parent_1, parent_2), child });
+		Ok(())
+	}
+
+	pub fn do_burn(owner: T::AccountId, kitty_id: [u8; 32]) -> DispatchResult {
+		let kitty = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?;
+		ensure!(kitty.owner == owner, Error::<T>::NotOwner);
+		// The bids in an auction are only settled once it ends.
+		ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+		ensure!(!KittyLocks::<T>::contains_key(kitty_id), Error::<T>::KittyLocked);
+		let new_count = CountForKitties::<T>::get().checked_sub(1).ok_or(Error::<T>::NoKitty)?;
+		let owned = CountForKittiesOwned::<T>::get(&owner)
+			.checked_sub(1)
+			.ok_or(Error::<T>::NoKitty)?;
+
+		// Nobody can accept the open offers anymore, so we refund them. Their entries in
+		// `OffersExpiringAt` are skipped once they expire.
+		for offer in Offers::<T>::take(kitty_id) {
+			let reason = HoldReason::Offer.into();
+			let _ = T::NativeBalance::release(
+				&reason,
+				&offer.bidder,
+				offer.amount,
+				Precision::BestEffort,
+			);
+		}
+		if let Some(deposit) = KittyDeposits::<T>::take(kitty_id) {
+			T::NativeBalance::release(
+				&HoldReason::KittyStorage.into(),
+				&owner,
+				deposit,
+				Precision::BestEffort,
+			)?;
+		}
+		Kitties::<T>::remove(kitty_id);
+		KittiesOwned::<T>::remove(&owner, kitty_id);
+		Self::set_count_owned(&owner, owned);
+		CountForKitties::<T>::set(new_count);
+		Creators::<T>::remove(kitty_id);
+		BreedingCooldowns::<T>::remove(kitty_id);
+		DutchAuctions::<T>::remove(kitty_id);
+		Approvals::<T>::remove(kitty_id);
+		BurnedKitties::<T>::insert(kitty_id, ());
+
+		Self::deposit_event(Event::<T>::Burned { owner, kitty_id });
+		Ok(())
+	}
+
+	// Starts an auction on behalf of `caller`, who must be the owner or one of their operators. The
+	// owner is always the seller, and is paid when the auction is settled.
+	pub fn do_start_auction(
+		caller: T::AccountId,
+		kitty_id: [u8; 32],
+		reserve: BalanceOf<T>,
+		duration: BlockNumberFor<T>,
+	) -> DispatchResult {
+		let mut kitty = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?;
+		ensure!(Self::is_owner_or_operator(&kitty.owner, &caller), Error::<T>::NotOwner);
+		ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+		ensure!(!KittyLocks::<T>::contains_key(kitty_id), Error::<T>::KittyLocked);
+		let seller = kitty.owner.clone();
+		ensure!(
+			!duration.is_zero() && duration <= T::MaxAuctionDuration::get(),
+			Error::<T>::InvalidAuctionDuration
+		);
+
+		let end = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+		AuctionsEndingAt::<T>::try_append(end, kitty_id)
+			.map_err(|_| Error::<T>::TooManyAuctions)?;
+		// A kitty in an auction cannot also be sold for a fixed or descending price.
+		kitty.price = None;
+		Kitties::<T>::insert(kitty_id, kitty);
+		DutchAuctions::<T>::remove(kitty_id);
+		let auction = Auction { seller: seller.clone(), reserve, end, highest_bid: None };
+		Auctions::<T>::insert(kitty_id, auction);
+
+		Self::deposit_event(Event::<T>::AuctionStarted { seller, kitty_id, reserve, end });
+		Ok(())
+	}
+
+	pub fn do_bid(
+		bidder: T::AccountId,
+		kitty_id: [u8; 32],
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let mut auction = Auctions::<T>::get(kitty_id).ok_or(Error::<T>::NoAuction)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(now < auction.end, Error::<T>::AuctionEnded);
+		ensure!(auction.seller != bidder, Error::<T>::BidOnOwnKitty);
+		ensure!(amount >= auction.reserve, Error::<T>::BidTooLow);
+
+		let reason = HoldReason::AuctionBid.into();
+		// Refund the previous highest bidder, who may also be the one raising their bid.
+		if let Some((previous_bidder, previous_amount)) = auction.highest_bid.take() {
+			ensure!(amount > previous_amount, Error::<T>::BidTooLow);
+			T::NativeBalance::release(
+				&reason,
+				&previous_bidder,
+				previous_amount,
+				Precision::Exact,
+			)?;
+		}
+		T::NativeBalance::hold(&reason, &bidder, amount)?;
+		auction.highest_bid = Some((bidder.clone(), amount));
+		Auctions::<T>::insert(kitty_id, auction);
+
+		Self::deposit_event(Event::<T>::BidPlaced { bidder, kitty_id, amount });
+		Ok(())
+	}
+
+	pub fn do_start_dutch_auction(
+		caller: T::AccountId,
+		kitty_id: [u8; 32],
+		start_price: BalanceOf<T>,
+		end_price: BalanceOf<T>,
+		duration: BlockNumberFor<T>,
+	) -> DispatchResult {
+		let mut kitty = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?;
+		ensure!(Self::is_owner_or_operator(&kitty.owner, &caller), Error::<T>::NotOwner);
+		ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+		ensure!(!KittyLocks::<T>::contains_key(kitty_id), Error::<T>::KittyLocked);
+		let seller = kitty.owner.clone();
+		ensure!(!duration.is_zero(), Error::<T>::InvalidAuctionDuration);
+		ensure!(start_price >= end_price, Error::<T>::InvalidDutchAuction);
+
+		let start = frame_system::Pallet::<T>::block_number();
+		let end = start.saturating_add(duration);
+		// The descending price replaces any fixed price listing.
+		kitty.price = None;
+		Kitties::<T>::insert(kitty_id, kitty);
+		DutchAuctions::<T>::insert(kitty_id, DutchAuction { start_price, end_price, start, end });
+
+		Self::deposit_event(Event::<T>::DutchAuctionStarted {
+			seller,
+			kitty_id,
+			start_price,
+			end_price,
+			end,
+		});
+		Ok(())
+	}
+
+	// Settles all auctions which end at block `n`, returning the weight used.
+	pub fn settle_auctions(n: BlockNumberFor<T>) -> Weight {
+		let ending = AuctionsEndingAt::<T>::take(n);
+		let settled = ending.len() as u32;
+		// Settling moves kitties and funds, so while sales are paused the auctions are postponed.
+		let paused = Paused::<T>::get().sale;
+		for kitty_id in ending {
+			if paused {
+				Self::postpone_auction(kitty_id, n);
+			} else {
+				Self::settle_auction(kitty_id);
+			}
+		}
+		T::WeightInfo::settle_auctions(settled)
+	}
+
+	// Moves the end of an auction which should have ended at block `n` to the next block with
+	// room for it, where it is settled if sales are no longer paused.
+	fn postpone_auction(kitty_id: [u8; 32], n: BlockNumberFor<T>) {
+		let Some(mut auction) = Auctions::<T>::get(kitty_id) else { return };
+		let mut end = n.saturating_add(One::one());
+		while AuctionsEndingAt::<T>::try_append(end, kitty_id).is_err() {
+			end = end.saturating_add(One::one());
+		}
+		auction.end = end;
+		Auctions::<T>::insert(kitty_id, auction);
+
+		Self::deposit_event(Event::<T>::AuctionPostponed { kitty_id, end });
+	}
+
+	// Ends an auction early without a sale, refunding the highest bid.
+	fn cancel_auction(kitty_id: [u8; 32], auction: Auction<T>) -> DispatchResult {
+		AuctionsEndingAt::<T>::mutate_exists(auction.end, |maybe_ending| {
+			if let Some(ending) = maybe_ending {
+				ending.retain(|id| *id != kitty_id);
+				if ending.is_empty() {
+					*maybe_ending = None;
+				}
+			}
+		});
+		if let Some((bidder, amount)) = auction.highest_bid {
+			let reason = HoldReason::AuctionBid.into();
+			T::NativeBalance::release(&reason, &bidder, amount, Precision::Exact)?;
+		}
+
+		Self::deposit_event(Event::<T>::AuctionCancelled { seller: auction.seller, kitty_id });
+		Ok(())
+	}
+
+	// Pays the seller with the held funds of the highest bidder, and transfers them the kitty.
+	// If this is not possible, the bid is refunded and the seller keeps the kitty.
+	fn settle_auction(kitty_id: [u8; 32]) {
+		let Some(auction) = Auctions::<T>::take(kitty_id) else { return };
+		let reason = HoldReason::AuctionBid.into();
+		let winning_bid = auction.highest_bid.and_then(|(bidder, amount)| {
+			let result = storage::with_storage_layer(|| {
+				let pay = |destination: &T::AccountId, amount: BalanceOf<T>| {
+					Self::pay_from_hold(&reason, &bidder, destination, amount)
+				};
+				Self::pay_for_sale(kitty_id, &auction.seller, amount, pay)?;
+				Self::do_transfer(auction.seller.clone(), bidder.clone(), kitty_id)
+			});
+			match result {
+				Ok(()) => Some((bidder, amount)),
+				Err(_) => {
+					let _ =
+						T::NativeBalance::release(&reason, &bidder, amount, Precision::BestEffort);
+					None
+				},
+			}
+		});
+
+		Self::deposit_event(Event::<T>::AuctionSettled {
+			seller: auction.seller,
+			kitty_id,
+			winning_bid,
+		});
+	}
+
+	pub fn do_make_offer(
+		bidder: T::AccountId,
+		kitty_id: [u8; 32],
+		amount: BalanceOf<T>,
+		expires_at: BlockNumberFor<T>,
+	) -> DispatchResult {
+		let kitty = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?;
+		ensure!(kitty.owner != bidder, Error::<T>::OfferOnOwnKitty);
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(expires_at > now, Error::<T>::OfferExpired);
+		ensure!(
+			expires_at <= now.saturating_add(T::MaxOfferDuration::get()),
+			Error::<T>::InvalidOfferDuration
+		);
+		ensure!(amount >= T::MinOfferAmount::get(), Error::<T>::OfferTooLow);
+
+		let reason = HoldReason::Offer.into();
+		let mut offers = Offers::<T>::get(kitty_id);
+		// A new offer replaces any previous offer from the same bidder.
+		if let Some(index) = offers.iter().position(|offer| offer.bidder == bidder) {
+			let previous = offers.swap_remove(index);
+			Self::remove_offer_expiry(kitty_id, &previous);
+			T::NativeBalance::release(&reason, &bidder, previous.amount, Precision::Exact)?;
+		} else if offers.is_full() {
+			// When the kitty has the maximum number of offers, a new offer must beat the lowest
+			// one, which is refunded and removed.
+			let (index, lowest) = offers
+				.iter()
+				.enumerate()
+				.min_by_key(|(_, offer)| offer.amount)
+				.map(|(index, offer)| (index, offer.amount))
+				.ok_or(Error::<T>::TooManyOffers)?;
+			ensure!(amount > lowest, Error::<T>::TooManyOffers);
+			let outbid = offers.swap_remove(index);
+			Self::remove_offer_expiry(kitty_id, &outbid);
+			T::NativeBalance::release(&reason, &outbid.bidder, outbid.amount, Precision::Exact)?;
+			Self::deposit_event(Event::<T>::OfferOutbid { bidder: outbid.bidder, kitty_id });
+		}
+		offers
+			.try_push(Offer { bidder: bidder.clone(), amount, expires_at })
+			.map_err(|_| Error::<T>::TooManyOffers)?;
+		OffersExpiringAt::<T>::try_append(expires_at, (kitty_id, bidder.clone()))
+			.map_err(|_| Error::<T>::TooManyOffers)?;
+		T::NativeBalance::hold(&reason, &bidder, amount)?;
+		Offers::<T>::insert(kitty_id, offers);
+
+		Self::deposit_event(Event::<T>::OfferMade { bidder, kitty_id, amount, expires_at });
+		Ok(())
+	}
+
+	pub fn do_withdraw_offer(bidder: T::AccountId, kitty_id: [u8; 32]) -> DispatchResult {
+		let offer = Self::take_offer(kitty_id, &bidder).ok_or(Error::<T>::NoOffer)?;
+		Self::remove_offer_expiry(kitty_id, &offer);
+		T::NativeBalance::release(
+			&HoldReason::Offer.into(),
+			&bidder,
+			offer.amount,
+			Precision::Exact,
+		)?;
+
+		Self::deposit_event(Event::<T>::OfferWithdrawn { bidder, kitty_id });
+		Ok(())
+	}
+
+	pub fn do_accept_offer(
+		owner: T::AccountId,
+		kitty_id: [u8; 32],
+		bidder: T::AccountId,
+	) -> DispatchResult {
+		let kitty = Kitties::<T>::get(kitty_id).ok_or(Error::<T>::NoKitty)?;
+		ensure!(kitty.owner == owner, Error::<T>::NotOwner);
+		let offer = Self::take_offer(kitty_id, &bidder).ok_or(Error::<T>::NoOffer)?;
+		Self::remove_offer_expiry(kitty_id, &offer);
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(offer.expires_at > now, Error::<T>::OfferExpired);
+
+		let reason = HoldReason::Offer.into();
+		let pay = |destination: &T::AccountId, amount: BalanceOf<T>| {
+			Self::pay_from_hold(&reason, &bidder, destination, amount)
+		};
+		Self::pay_for_sale(kitty_id, &owner, offer.amount, pay)?;
+		Self::do_transfer(owner.clone(), bidder.clone(), kitty_id)?;
+
+		Self::deposit_event(Event::<T>::OfferAccepted {
+			owner,
+			bidder,
+			kitty_id,
+			amount: offer.amount,
+		});
+		Ok(())
+	}
+
+	// Pays `amount` out of the funds `payer` holds for `reason`, as free balance of `destination`.
+	fn pay_from_hold(
+		reason: &T::RuntimeHoldReason,
+		payer: &T::AccountId,
+		destination: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		T::NativeBalance::transfer_on_hold(
+			reason,
+			payer,
+			destination,
+			amount,
+			Precision::Exact,
+			Restriction::Free,
+			Fortitude::Polite,
+		)
+		.map(|_| ())
+	}
+
+	// Removes and returns the offer of `bidder` on a kitty, if there is one.
+	fn take_offer(kitty_id: [u8; 32], bidder: &T::AccountId) -> Option<Offer<T>> {
+		let mut offers = Offers::<T>::get(kitty_id);
+		let index = offers.iter().position(|offer| &offer.bidder == bidder)?;
+		let offer = offers.swap_remove(index);
+		if offers.is_empty() {
+			Offers::<T>::remove(kitty_id);
+		} else {
+			Offers::<T>::insert(kitty_id, offers);
+		}
+		Some(offer)
+	}
+
+	// Removes the entry of an offer from `OffersExpiringAt`, so it does not use up the capacity of
+	// that block once the offer is gone.
+	fn remove_offer_expiry(kitty_id: [u8; 32], offer: &Offer<T>) {
+		OffersExpiringAt::<T>::mutate_exists(offer.expires_at, |maybe_expiring| {
+			if let Some(expiring) = maybe_expiring {
+				expiring.retain(|(id, bidder)| *id != kitty_id || *bidder != offer.bidder);
+				if expiring.is_empty() {
+					*maybe_expiring = None;
+				}
+			}
+		});
+	}
+
+	// Removes all offers which expire at block `n` and refunds the bidders, returning the weight
+	// used. Unlike auctions, offers expire while sales are paused, since no kitty changes hands.
+	pub fn expire_offers(n: BlockNumberFor<T>) -> Weight {
+		let expiring = OffersExpiringAt::<T>::take(n);
+		let checked = expiring.len() as u32;
+		for (kitty_id, bidder) in expiring {
+			// The kitty may have been burned since the offer was made.
+			let expired = Offers::<T>::get(kitty_id)
+				.iter()
+				.any(|offer| offer.bidder == bidder && offer.expires_at == n);
+			if !expired {
+				continue
+			}
+			if let Some(offer) = Self::take_offer(kitty_id, &bidder) {
+				let reason = HoldReason::Offer.into();
+				let _ = T::NativeBalance::release(
+					&reason,
+					&bidder,
+					offer.amount,
+					Precision::BestEffort,
+				);
+				Self::deposit_event(Event::<T>::OfferExpired { bidder, kitty_id });
+			}
+		}
+		T::WeightInfo::expire_offers(checked)
+	}
+}
+
+// Helper functions used to implement the `KittiesApi` Runtime API.
+impl<T: Config> Pallet<T> {
+	pub fn kitty(id: [u8; 32]) -> Option<KittyInfo<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>> {
+		// Like `listed_for_sale`, a kitty in a Dutch auction reports its current price.
+		Kitties::<T>::get(id).map(|kitty| KittyInfo {
+			price: Self::current_price(id, &kitty),
+			dna: kitty.dna.into(),
+			owner: kitty.owner,
+			parents: kitty.parents,
+			generation: kitty.generation,
+			born_at: kitty.born_at,
+		})
+	}
+
+	pub fn kitties_of(owner: T::AccountId) -> Vec<[u8; 32]> {
+		KittiesOwned::<T>::iter_key_prefix(owner).collect()
+	}
+
+	pub fn listed_for_sale(start: u32, limit: u32) -> Vec<([u8; 32], BalanceOf<T>)> {
+		Kitties::<T>::iter()
+			.filter_map(|(id, kitty)| Self::current_price(id, &kitty).map(|price| (id, price)))
+			.skip(start as usize)
+			.take(limit as usize)
+			.collect()
+	}
+
+	pub fn total_count() -> u32 {
+		CountForKitties::<T>::get()
+	}
+}
+
+// Checks of the invariants of our storage, used by `try_state` and after every test.
+#[cfg(any(feature = "try-runtime", test))]
+impl<T: Config> Pallet<T> {
+	// Ownership is recorded in both `Kitties` and `KittiesOwned`, so a bug in any of the functions
+	// which move kitties between owners could otherwise corrupt it silently.
+	pub fn do_try_state() -> Result<(), TryRuntimeError> {
+		let mut count = 0u32;
+		for (kitty_id, kitty) in Kitties::<T>::iter() {
+			ensure!(
+				KittiesOwned::<T>::contains_key(&kitty.owner, kitty_id),
+				"a kitty is missing from the kitties owned by its owner"
+			);
+			count.saturating_inc();
+		}
+		ensure!(
+			count == CountForKitties::<T>::get(),
+			"`CountForKitties` does not match the number of kitties"
+		);
+
+		// Since every kitty has a single owner, this also means no kitty is owned more than once.
+		let mut owned = BTreeMap::<T::AccountId, u32>::new();
+		for (owner, kitty_id, ()) in KittiesOwned::<T>::iter() {
+			let kitty =
+				Kitties::<T>::get(kitty_id).ok_or("an owner has a kitty which does not exist")?;
+			ensure!(kitty.owner == owner, "an owner has a kitty which belongs to someone else");
+			owned.entry(owner).or_default().saturating_inc();
+		}
+		for (owner, count) in CountForKittiesOwned::<T>::iter() {
+			ensure!(count > 0, "an account which owns no kitties has a count stored");
+			ensure!(
+				owned.remove(&owner).unwrap_or_default() == count,
+				"`CountForKittiesOwned` does not match the kitties owned by an account"
+			);
+		}
+		ensure!(owned.is_empty(), "an account owns kitties which are not counted");
+
+		for kitty_id in Approvals::<T>::iter_keys() {
+			ensure!(
+				Kitties::<T>::contains_key(kitty_id),
+				"a kitty which does not exist is approved"
+			);
+		}
+		for kitty_id in KittyLocks::<T>::iter_keys() {
+			ensure!(Kitties::<T>::contains_key(kitty_id), "a kitty which does not exist is locked");
+		}
+		for kitty_id in BurnedKitties::<T>::iter_keys() {
+			ensure!(!Kitties::<T>::contains_key(kitty_id), "a burned kitty still exists");
+		}
+
+		let mut deposits = BTreeMap::<T::AccountId, BalanceOf<T>>::new();
+		for (kitty_id, deposit) in KittyDeposits::<T>::iter() {
+			let kitty = Kitties::<T>::get(kitty_id)
+				.ok_or("a deposit is held for a kitty which does not exist")?;
+			deposits.entry(kitty.owner).or_default().saturating_accrue(deposit);
+		}
+		let reason = HoldReason::KittyStorage.into();
+		for (owner, deposit) in deposits {
+			ensure!(
+				T::NativeBalance::balance_on_hold(&reason, &owner) == deposit,
+				"the deposits held from an owner do not match the deposits of their kitties"
+			);
+		}
 		Ok(())
 	}
 }
@@ -1,18 +1,46 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod dna;
 mod impls;
+mod locks;
+pub mod migrations;
+mod nonfungible;
+mod pause;
+pub mod runtime_api;
 mod tests;
+pub mod weights;
 
+use alloc::vec::Vec;
+pub use dna::Dna;
+use frame::arithmetic::Permill;
+#[cfg(feature = "try-runtime")]
+use frame::deps::sp_runtime::TryRuntimeError;
 use frame::prelude::*;
 use frame::traits::fungible::Inspect;
 use frame::traits::fungible::Mutate;
+use frame::traits::fungible::MutateHold;
+use frame::traits::LockIdentifier;
+pub use locks::KittyLocker;
 pub use pallet::*;
+pub use pause::PauseFilter;
+pub use weights::WeightInfo;
 
-#[frame::pallet(dev_mode)]
+// The `TryState` impl which `#[pallet::hooks]` generates next to our hooks logs errors with
+// `map_err`, which clippy flags under the `try-runtime` feature.
+#[cfg_attr(feature = "try-runtime", allow(clippy::manual_inspect))]
+#[frame::pallet]
 pub mod pallet {
 	use super::*;
 
+	/// The current storage version of the pallet.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(core::marker::PhantomData<T>);
 
 	#[pallet::config]
@@ -20,43 +48,468 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
 		/// The Fungible handler for the kitties pallet.
-		type NativeBalance: Inspect<Self::AccountId> + Mutate<Self::AccountId>;
+		type NativeBalance: Inspect<Self::AccountId>
+			+ Mutate<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The overarching hold reason, which includes the `HoldReason` of this pallet.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// Weight information for the calls of this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// The maximum number of kitties a single account can own.
+		///
+		/// Accounts which already own more kitties than this keep them, but cannot receive more.
+		#[pallet::constant]
+		type MaxKittiesOwned: Get<u32>;
+
+		/// The number of blocks a kitty must rest after breeding for the first time.
+		#[pallet::constant]
+		type BreedingCooldownBase: Get<BlockNumberFor<Self>>;
+
+		/// The factor the cooldown is multiplied by for every previous breeding of a kitty.
+		#[pallet::constant]
+		type BreedingCooldownFactor: Get<u32>;
+
+		/// The longest a kitty will ever need to rest after breeding.
+		#[pallet::constant]
+		type MaxBreedingCooldown: Get<BlockNumberFor<Self>>;
+
+		/// The longest an auction can run for.
+		#[pallet::constant]
+		type MaxAuctionDuration: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of auctions which can end in the same block.
+		#[pallet::constant]
+		type MaxAuctionsPerBlock: Get<u32>;
+
+		/// The maximum number of open offers on a single kitty.
+		#[pallet::constant]
+		type MaxOffersPerKitty: Get<u32>;
+
+		/// The maximum number of offers which can expire in the same block.
+		#[pallet::constant]
+		type MaxOffersPerBlock: Get<u32>;
+
+		/// The minimum amount of an offer.
+		#[pallet::constant]
+		type MinOfferAmount: Get<BalanceOf<Self>>;
+
+		/// The maximum number of blocks an offer can stay open.
+		#[pallet::constant]
+		type MaxOfferDuration: Get<BlockNumberFor<Self>>;
+
+		/// The share of every sale which is paid to the creator of the kitty.
+		#[pallet::constant]
+		type RoyaltyPercent: Get<Permill>;
+
+		/// The share of every sale which is taken as a marketplace fee.
+		#[pallet::constant]
+		type MarketplaceFee: Get<Permill>;
+
+		/// The account which receives the marketplace fees, for example a treasury.
+		type FeeDestination: Get<Self::AccountId>;
+
+		/// The deposit held from the minter of a kitty for the storage it uses.
+		///
+		/// The deposit moves with the kitty to every new owner, and is released when it is burned.
+		/// This means kitties can only be sent to accounts which already exist.
+		#[pallet::constant]
+		type KittyDeposit: Get<BalanceOf<Self>>;
+
+		/// The account which receives the price paid to create a kitty.
+		///
+		/// It also pays the deposit for kitties created by the admin origin with `force_mint`.
+		type MintBeneficiary: Get<Self::AccountId>;
+
+		/// The origin which can set the mint price, and force the creation, transfer and pricing of
+		/// kitties, for example governance.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+	}
+
+	/// The reasons this pallet places a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The funds are the highest bid in an auction.
+		AuctionBid,
+		/// The funds are offered to the owner of a kitty.
+		Offer,
+		/// The funds are a deposit for the storage used by a kitty.
+		KittyStorage,
 	}
 
 	// Allows easy access our Pallet's `Balance` type. Comes from `Fungible` interface.
 	pub type BalanceOf<T> =
 		<<T as Config>::NativeBalance as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
+	// A kitty which exists at genesis, as `(owner, dna, price)`.
+	pub type GenesisKitty<T> =
+		(<T as frame_system::Config>::AccountId, [u8; 32], Option<BalanceOf<T>>);
+
 	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen)]
 	#[scale_info(skip_type_params(T))]
 	pub struct Kitty<T: Config> {
-		// Using 32 bytes to represent a kitty DNA
-		pub dna: [u8; 32],
+		// Using 32 bytes to represent a kitty DNA, see the `dna` module for how it is decoded.
+		pub dna: Dna,
 		pub owner: T::AccountId,
 		pub price: Option<BalanceOf<T>>,
+		// The DNA of both parents, or `None` for kitties which were not bred.
+		pub parents: Option<([u8; 32], [u8; 32])>,
+		// Generation `0` kitties have no parents, children are one generation above their parents.
+		pub generation: u16,
+		pub born_at: BlockNumberFor<T>,
+	}
+
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, DefaultNoBound)]
+	#[scale_info(skip_type_params(T))]
+	pub struct BreedingStatus<T: Config> {
+		// How many times this kitty has been used as a parent.
+		pub times_bred: u32,
+		// The first block where this kitty can breed again.
+		pub ready_at: BlockNumberFor<T>,
+	}
+
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Auction<T: Config> {
+		pub seller: T::AccountId,
+		// The lowest bid the seller will accept.
+		pub reserve: BalanceOf<T>,
+		// The block where the auction is settled.
+		pub end: BlockNumberFor<T>,
+		// The highest bidder and their bid, which is held until the auction is settled.
+		pub highest_bid: Option<(T::AccountId, BalanceOf<T>)>,
+	}
+
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct DutchAuction<T: Config> {
+		pub start_price: BalanceOf<T>,
+		pub end_price: BalanceOf<T>,
+		// The price decays linearly from `start_price` at `start` to `end_price` at `end`.
+		pub start: BlockNumberFor<T>,
+		pub end: BlockNumberFor<T>,
+	}
+
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Offer<T: Config> {
+		pub bidder: T::AccountId,
+		// The amount offered, which is held until the offer is accepted, withdrawn or expires.
+		pub amount: BalanceOf<T>,
+		// The block where the offer expires.
+		pub expires_at: BlockNumberFor<T>,
+	}
+
+	// Each flag pauses a group of calls, see `PauseFilter` for which calls are in each group.
+	#[derive(
+		Clone, Copy, PartialEq, Eq, Default, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug,
+	)]
+	pub struct PauseFlags {
+		// Pauses the creation and breeding of kitties.
+		pub mint: bool,
+		// Pauses transfers between accounts.
+		pub transfer: bool,
+		// Pauses listings, auctions and offers.
+		pub sale: bool,
+	}
+
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct KittyLock<T: Config> {
+		// The account which placed the lock, and is the only one who can remove it.
+		pub owner: T::AccountId,
+		// Why the kitty is locked, for example staking or a breeding rental.
+		pub reason: LockIdentifier,
+	}
+
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Approval<T: Config> {
+		// The account which may transfer the kitty on behalf of its owner.
+		pub delegate: T::AccountId,
+		// The block where the approval expires, if it ever does.
+		pub expires_at: Option<BlockNumberFor<T>>,
 	}
 
 	#[pallet::storage]
 	pub(super) type CountForKitties<T: Config> = StorageValue<Value = u32, QueryKind = ValueQuery>;
 
+	/// The parts of the pallet which are paused by the admin origin. Nothing is paused by default.
+	#[pallet::storage]
+	pub(super) type Paused<T: Config> = StorageValue<Value = PauseFlags, QueryKind = ValueQuery>;
+
+	/// The price paid to the `MintBeneficiary` to create a new kitty. Creating kitties is free
+	/// until it is set.
+	#[pallet::storage]
+	pub(super) type MintPrice<T: Config> =
+		StorageValue<Value = BalanceOf<T>, QueryKind = ValueQuery>;
+
+	#[pallet::storage]
+	pub(super) type Kitties<T: Config> =
+		StorageMap<Hasher = Blake2_128Concat, Key = [u8; 32], Value = Kitty<T>>;
+
+	/// Track the account which originally minted each kitty.
+	#[pallet::storage]
+	pub(super) type Creators<T: Config> =
+		StorageMap<Hasher = Blake2_128Concat, Key = [u8; 32], Value = T::AccountId>;
+
+	/// Track the DNA of every kitty which has been burned, so it can never be minted again.
 	#[pallet::storage]
-	pub(super) type Kitties<T: Config> = StorageMap<Key = [u8; 32], Value = Kitty<T>>;
+	pub(super) type BurnedKitties<T: Config> =
+		StorageMap<Hasher = Blake2_128Concat, Key = [u8; 32], Value = ()>;
 
 	/// Track the kitties owned by each account.
+	///
+	/// Each kitty is a separate entry, so moving a kitty costs the same however many kitties its
+	/// owners have.
+	#[pallet::storage]
+	pub(super) type KittiesOwned<T: Config> = StorageDoubleMap<
+		Hasher1 = Blake2_128Concat,
+		Key1 = T::AccountId,
+		Hasher2 = Blake2_128Concat,
+		Key2 = [u8; 32],
+		Value = (),
+	>;
+
+	/// Track the number of kitties owned by each account.
 	#[pallet::storage]
-	pub(super) type KittiesOwned<T: Config> = StorageMap<
+	pub(super) type CountForKittiesOwned<T: Config> = StorageMap<
+		Hasher = Blake2_128Concat,
 		Key = T::AccountId,
-		Value = BoundedVec<[u8; 32], ConstU32<100>>,
+		Value = u32,
+		QueryKind = ValueQuery,
+	>;
+
+	/// Track the breeding cooldown of each kitty.
+	#[pallet::storage]
+	pub(super) type BreedingCooldowns<T: Config> = StorageMap<
+		Hasher = Blake2_128Concat,
+		Key = [u8; 32],
+		Value = BreedingStatus<T>,
+		QueryKind = ValueQuery,
+	>;
+
+	/// Track the kitties which are currently being auctioned.
+	#[pallet::storage]
+	pub(super) type Auctions<T: Config> =
+		StorageMap<Hasher = Blake2_128Concat, Key = [u8; 32], Value = Auction<T>>;
+
+	/// Track the auctions which need to be settled at each block.
+	#[pallet::storage]
+	pub(super) type AuctionsEndingAt<T: Config> = StorageMap<
+		Hasher = Blake2_128Concat,
+		Key = BlockNumberFor<T>,
+		Value = BoundedVec<[u8; 32], T::MaxAuctionsPerBlock>,
 		QueryKind = ValueQuery,
 	>;
 
+	/// Track the kitties which are for sale with a descending price.
+	#[pallet::storage]
+	pub(super) type DutchAuctions<T: Config> =
+		StorageMap<Hasher = Blake2_128Concat, Key = [u8; 32], Value = DutchAuction<T>>;
+
+	/// Track the open offers on each kitty.
+	#[pallet::storage]
+	pub(super) type Offers<T: Config> = StorageMap<
+		Hasher = Blake2_128Concat,
+		Key = [u8; 32],
+		Value = BoundedVec<Offer<T>, T::MaxOffersPerKitty>,
+		QueryKind = ValueQuery,
+	>;
+
+	/// Track the offers which expire at each block.
+	#[pallet::storage]
+	pub(super) type OffersExpiringAt<T: Config> = StorageMap<
+		Hasher = Blake2_128Concat,
+		Key = BlockNumberFor<T>,
+		Value = BoundedVec<([u8; 32], T::AccountId), T::MaxOffersPerBlock>,
+		QueryKind = ValueQuery,
+	>;
+
+	/// Track the account approved to transfer each kitty on behalf of its owner.
+	#[pallet::storage]
+	pub(super) type Approvals<T: Config> =
+		StorageMap<Hasher = Blake2_128Concat, Key = [u8; 32], Value = Approval<T>>;
+
+	/// Track the deposit held for each kitty, which is owed back to its owner when it is burned.
+	///
+	/// Kitties minted before deposits were introduced have no entry.
+	#[pallet::storage]
+	pub(super) type KittyDeposits<T: Config> =
+		StorageMap<Hasher = Blake2_128Concat, Key = [u8; 32], Value = BalanceOf<T>>;
+
+	/// Track the kitties which are locked, and so cannot be transferred, listed, bought or burned.
+	#[pallet::storage]
+	pub(super) type KittyLocks<T: Config> =
+		StorageMap<Hasher = Blake2_128Concat, Key = [u8; 32], Value = KittyLock<T>>;
+
+	/// Track the operators approved to manage all of the kitties of an owner, as
+	/// `(owner, operator)`.
+	#[pallet::storage]
+	pub(super) type OperatorApprovals<T: Config> = StorageDoubleMap<
+		Hasher1 = Blake2_128Concat,
+		Key1 = T::AccountId,
+		Hasher2 = Blake2_128Concat,
+		Key2 = T::AccountId,
+		Value = (),
+	>;
+
+	/// The kitties which exist at genesis.
+	#[pallet::genesis_config]
+	#[derive(DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		pub kitties: Vec<GenesisKitty<T>>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			// We go through the same logic as the extrinsics, so all of our storage stays
+			// consistent.
+			for (owner, dna, price) in &self.kitties {
+				Pallet::<T>::mint(owner.clone(), *dna, None)
+					.expect("genesis kitties must be unique, and within the owned limit");
+				if price.is_some() {
+					Pallet::<T>::do_set_price(owner.clone(), *dna, *price)
+						.expect("the owner just minted this kitty; qed");
+				}
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		Created { owner: T::AccountId },
-		Transferred { from: T::AccountId, to: T::AccountId, kitty_id: [u8; 32] },
-		PriceSet { owner: T::AccountId, kitty_id: [u8; 32], new_price: Option<BalanceOf<T>> },
-		Sold { buyer: T::AccountId, kitty_id: [u8; 32], price: BalanceOf<T> },
+		Created {
+			owner: T::AccountId,
+		},
+		Transferred {
+			from: T::AccountId,
+			to: T::AccountId,
+			kitty_id: [u8; 32],
+		},
+		PriceSet {
+			owner: T::AccountId,
+			kitty_id: [u8; 32],
+			new_price: Option<BalanceOf<T>>,
+		},
+		Sold {
+			buyer: T::AccountId,
+			kitty_id: [u8; 32],
+			price: BalanceOf<T>,
+			royalty: BalanceOf<T>,
+			fee: BalanceOf<T>,
+		},
+		Bred {
+			owner: T::AccountId,
+			parents: ([u8; 32], [u8; 32]),
+			child: [u8; 32],
+		},
+		AuctionStarted {
+			seller: T::AccountId,
+			kitty_id: [u8; 32],
+			reserve: BalanceOf<T>,
+			end: BlockNumberFor<T>,
+		},
+		DutchAuctionStarted {
+			seller: T::AccountId,
+			kitty_id: [u8; 32],
+			start_price: BalanceOf<T>,
+			end_price: BalanceOf<T>,
+			end: BlockNumberFor<T>,
+		},
+		BidPlaced {
+			bidder: T::AccountId,
+			kitty_id: [u8; 32],
+			amount: BalanceOf<T>,
+		},
+		OfferMade {
+			bidder: T::AccountId,
+			kitty_id: [u8; 32],
+			amount: BalanceOf<T>,
+			expires_at: BlockNumberFor<T>,
+		},
+		OfferWithdrawn {
+			bidder: T::AccountId,
+			kitty_id: [u8; 32],
+		},
+		OfferAccepted {
+			owner: T::AccountId,
+			bidder: T::AccountId,
+			kitty_id: [u8; 32],
+			amount: BalanceOf<T>,
+		},
+		OfferExpired {
+			bidder: T::AccountId,
+			kitty_id: [u8; 32],
+		},
+		OfferOutbid {
+			bidder: T::AccountId,
+			kitty_id: [u8; 32],
+		},
+		AuctionSettled {
+			seller: T::AccountId,
+			kitty_id: [u8; 32],
+			winning_bid: Option<(T::AccountId, BalanceOf<T>)>,
+		},
+		AuctionCancelled {
+			seller: T::AccountId,
+			kitty_id: [u8; 32],
+		},
+		AuctionPostponed {
+			kitty_id: [u8; 32],
+			end: BlockNumberFor<T>,
+		},
+		Burned {
+			owner: T::AccountId,
+			kitty_id: [u8; 32],
+		},
+		TransferApproved {
+			owner: T::AccountId,
+			kitty_id: [u8; 32],
+			delegate: T::AccountId,
+			expires_at: Option<BlockNumberFor<T>>,
+		},
+		ApprovalCancelled {
+			owner: T::AccountId,
+			kitty_id: [u8; 32],
+		},
+		ApprovalForAllSet {
+			owner: T::AccountId,
+			operator: T::AccountId,
+			approved: bool,
+		},
+		MintPriceUpdated {
+			price: BalanceOf<T>,
+		},
+		ForceMinted {
+			owner: T::AccountId,
+			kitty_id: [u8; 32],
+		},
+		ForceTransferred {
+			from: T::AccountId,
+			to: T::AccountId,
+			kitty_id: [u8; 32],
+		},
+		ForcePriceSet {
+			owner: T::AccountId,
+			kitty_id: [u8; 32],
+			new_price: Option<BalanceOf<T>>,
+		},
+		PausedUpdated {
+			paused: PauseFlags,
+		},
+		Locked {
+			kitty_id: [u8; 32],
+			lock_owner: T::AccountId,
+			reason: LockIdentifier,
+		},
+		Unlocked {
+			kitty_id: [u8; 32],
+			lock_owner: T::AccountId,
+		},
 	}
 
 	#[pallet::error]
@@ -69,45 +522,291 @@ pub mod pallet {
 		NotOwner,
 		NotForSale,
 		MaxPriceTooLow,
+		BreedWithSelf,
+		KittyOnCooldown,
+		KittyInAuction,
+		NoAuction,
+		InvalidAuctionDuration,
+		TooManyAuctions,
+		AuctionEnded,
+		BidOnOwnKitty,
+		BidTooLow,
+		InvalidDutchAuction,
+		OfferOnOwnKitty,
+		OfferExpired,
+		OfferTooLow,
+		InvalidOfferDuration,
+		TooManyOffers,
+		NoOffer,
+		KittyBurned,
+		ApproveSelf,
+		NoApproval,
+		NotDelegate,
+		ApprovalExpired,
+		MintingPaused,
+		TransfersPaused,
+		SalesPaused,
+		KittyLocked,
+		NotLocked,
+		NotLockOwner,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			Self::settle_auctions(n).saturating_add(Self::expire_offers(n))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+			Self::do_try_state()
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::create_kitty())]
 		pub fn create_kitty(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let dna = Self::gen_dna();
-			Self::mint(who, dna)?;
+			ensure!(!Paused::<T>::get().mint, Error::<T>::MintingPaused);
+			Self::do_create_kitty(who)?;
 			Ok(())
 		}
 
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::transfer(T::MaxKittiesOwned::get()))]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			to: T::AccountId,
 			kitty_id: [u8; 32],
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			ensure!(!Paused::<T>::get().transfer, Error::<T>::TransfersPaused);
 			Self::do_transfer(who, to, kitty_id)?;
 			Ok(())
 		}
 
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_price())]
 		pub fn set_price(
 			origin: OriginFor<T>,
 			kitty_id: [u8; 32],
 			new_price: Option<BalanceOf<T>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			ensure!(!Paused::<T>::get().sale, Error::<T>::SalesPaused);
 			Self::do_set_price(who, kitty_id, new_price)?;
 			Ok(())
 		}
 
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::buy_kitty())]
 		pub fn buy_kitty(
 			origin: OriginFor<T>,
 			kitty_id: [u8; 32],
 			max_price: BalanceOf<T>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			ensure!(!Paused::<T>::get().sale, Error::<T>::SalesPaused);
 			Self::do_buy_kitty(who, kitty_id, max_price)?;
 			Ok(())
 		}
+
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::breed())]
+		pub fn breed(
+			origin: OriginFor<T>,
+			parent_1: [u8; 32],
+			parent_2: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Paused::<T>::get().mint, Error::<T>::MintingPaused);
+			Self::do_breed(who, parent_1, parent_2)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::start_auction())]
+		pub fn start_auction(
+			origin: OriginFor<T>,
+			kitty_id: [u8; 32],
+			reserve: BalanceOf<T>,
+			duration: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Paused::<T>::get().sale, Error::<T>::SalesPaused);
+			Self::do_start_auction(who, kitty_id, reserve, duration)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::bid())]
+		pub fn bid(
+			origin: OriginFor<T>,
+			kitty_id: [u8; 32],
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Paused::<T>::get().sale, Error::<T>::SalesPaused);
+			Self::do_bid(who, kitty_id, amount)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::start_dutch_auction())]
+		pub fn start_dutch_auction(
+			origin: OriginFor<T>,
+			kitty_id: [u8; 32],
+			start_price: BalanceOf<T>,
+			end_price: BalanceOf<T>,
+			duration: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Paused::<T>::get().sale, Error::<T>::SalesPaused);
+			Self::do_start_dutch_auction(who, kitty_id, start_price, end_price, duration)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::make_offer())]
+		pub fn make_offer(
+			origin: OriginFor<T>,
+			kitty_id: [u8; 32],
+			amount: BalanceOf<T>,
+			expires_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Paused::<T>::get().sale, Error::<T>::SalesPaused);
+			Self::do_make_offer(who, kitty_id, amount, expires_at)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::withdraw_offer())]
+		pub fn withdraw_offer(origin: OriginFor<T>, kitty_id: [u8; 32]) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_withdraw_offer(who, kitty_id)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::accept_offer())]
+		pub fn accept_offer(
+			origin: OriginFor<T>,
+			kitty_id: [u8; 32],
+			bidder: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Paused::<T>::get().sale, Error::<T>::SalesPaused);
+			Self::do_accept_offer(who, kitty_id, bidder)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::burn())]
+		pub fn burn(origin: OriginFor<T>, kitty_id: [u8; 32]) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_burn(who, kitty_id)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::approve_transfer())]
+		pub fn approve_transfer(
+			origin: OriginFor<T>,
+			kitty_id: [u8; 32],
+			delegate: T::AccountId,
+			maybe_expiry: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_approve_transfer(who, kitty_id, delegate, maybe_expiry)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::cancel_approval())]
+		pub fn cancel_approval(origin: OriginFor<T>, kitty_id: [u8; 32]) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_cancel_approval(who, kitty_id)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			kitty_id: [u8; 32],
+			to: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Paused::<T>::get().transfer, Error::<T>::TransfersPaused);
+			Self::do_transfer_from(who, kitty_id, to)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::set_approval_for_all())]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			operator: T::AccountId,
+			approved: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_set_approval_for_all(who, operator, approved)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::set_mint_price())]
+		pub fn set_mint_price(origin: OriginFor<T>, price: BalanceOf<T>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::do_set_mint_price(price);
+			Ok(())
+		}
+
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::force_mint())]
+		pub fn force_mint(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			dna: [u8; 32],
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::do_force_mint(owner, dna)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::force_transfer())]
+		pub fn force_transfer(
+			origin: OriginFor<T>,
+			kitty_id: [u8; 32],
+			to: T::AccountId,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::do_force_transfer(kitty_id, to)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::force_set_price())]
+		pub fn force_set_price(
+			origin: OriginFor<T>,
+			kitty_id: [u8; 32],
+			new_price: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::do_force_set_price(kitty_id, new_price)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::set_paused())]
+		pub fn set_paused(origin: OriginFor<T>, paused: PauseFlags) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::do_set_paused(paused);
+			Ok(())
+		}
 	}
 }
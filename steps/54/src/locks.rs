@@ -0,0 +1,66 @@
+// Locks for kitties which are in use by other pallets.
+//
+// A pallet like a staking game or a breeding rental needs to be sure a kitty does not move while
+// it is in use. It can lock the kitty through the `KittyLocker` trait, which stops the kitty from
+// being transferred, listed, bought or burned until the same account unlocks it. The owner keeps
+// the kitty the whole time, and can still breed it and receive offers for it.
+//
+// Not even the admin origin can move a locked kitty, since the pallet which placed the lock is
+// never told about it. A `force_transfer` of a locked kitty fails until the lock owner unlocks it.
+//
+// There are no calls to lock a kitty, since the owner could then unlock it whenever they like.
+// Instead, the runtime connects the pallets which need locks to our pallet, for example with
+// `type Kitties = PalletKitties;`.
+
+use super::*;
+
+/// An interface for other pallets to lock kitties.
+pub trait KittyLocker<AccountId> {
+	/// Locks `kitty_id` for `reason`, so that it cannot be transferred, listed, bought or burned
+	/// until `lock_owner` unlocks it, not even by the admin origin. A kitty can only hold one lock
+	/// at a time.
+	fn lock(kitty_id: &[u8; 32], lock_owner: &AccountId, reason: LockIdentifier) -> DispatchResult;
+
+	/// Removes the lock on `kitty_id`, which must have been placed by `lock_owner`.
+	fn unlock(kitty_id: &[u8; 32], lock_owner: &AccountId) -> DispatchResult;
+
+	/// Returns the account which locked `kitty_id` and the reason, if it is locked.
+	fn lock_of(kitty_id: &[u8; 32]) -> Option<(AccountId, LockIdentifier)>;
+}
+
+impl<T: Config> KittyLocker<T::AccountId> for Pallet<T> {
+	fn lock(
+		kitty_id: &[u8; 32],
+		lock_owner: &T::AccountId,
+		reason: LockIdentifier,
+	) -> DispatchResult {
+		ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::NoKitty);
+		ensure!(!KittyLocks::<T>::contains_key(kitty_id), Error::<T>::KittyLocked);
+		// The kitty would be stuck in the auction, since it could not be given to the winner.
+		ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+		KittyLocks::<T>::insert(kitty_id, KittyLock { owner: lock_owner.clone(), reason });
+
+		Self::deposit_event(Event::<T>::Locked {
+			kitty_id: *kitty_id,
+			lock_owner: lock_owner.clone(),
+			reason,
+		});
+		Ok(())
+	}
+
+	fn unlock(kitty_id: &[u8; 32], lock_owner: &T::AccountId) -> DispatchResult {
+		let lock = KittyLocks::<T>::get(kitty_id).ok_or(Error::<T>::NotLocked)?;
+		ensure!(lock.owner == *lock_owner, Error::<T>::NotLockOwner);
+		KittyLocks::<T>::remove(kitty_id);
+
+		Self::deposit_event(Event::<T>::Unlocked {
+			kitty_id: *kitty_id,
+			lock_owner: lock_owner.clone(),
+		});
+		Ok(())
+	}
+
+	fn lock_of(kitty_id: &[u8; 32]) -> Option<(T::AccountId, LockIdentifier)> {
+		KittyLocks::<T>::get(kitty_id).map(|lock| (lock.owner, lock.reason))
+	}
+}
@@ -0,0 +1,163 @@
+// Storage migrations for the Kitties Pallet.
+//
+// Whenever the layout of something in storage changes, the existing data needs to be translated
+// into the new layout as part of the runtime upgrade, otherwise it can no longer be decoded.
+//
+// Each migration is written as an `UncheckedOnRuntimeUpgrade`, and then wrapped in a
+// `VersionedMigration`. The wrapper only runs the migration when the on-chain storage version
+// matches, and bumps the version afterwards, so a migration can never run twice. Runtimes should
+// only ever use the wrapped migration.
+//
+// With the `try-runtime` feature enabled, each migration also checks the state of storage before
+// and after it runs.
+//
+// Learn more about storage migrations:
+// https://paritytech.github.io/polkadot-sdk/master/polkadot_sdk_docs/reference_docs/frame_runtime_upgrades_and_migrations/index.html
+
+use super::*;
+use alloc::vec::Vec;
+use frame::arithmetic::Saturating;
+use frame::arithmetic::Zero;
+use frame::deps::frame_support::migrations::VersionedMigration;
+use frame::deps::frame_support::storage_alias;
+#[cfg(feature = "try-runtime")]
+use frame::deps::sp_runtime::TryRuntimeError;
+use frame::prelude::*;
+use frame::traits::UncheckedOnRuntimeUpgrade;
+
+/// Migrates `Kitties` from storage version 0 to 1.
+///
+/// Version 1 adds the `parents`, `generation` and `born_at` fields to `Kitty`.
+pub mod v1 {
+	use super::*;
+
+	/// The layout of a `Kitty` in storage version 0.
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct OldKitty<T: Config> {
+		pub dna: [u8; 32],
+		pub owner: T::AccountId,
+		pub price: Option<BalanceOf<T>>,
+	}
+
+	/// Translates every kitty into the version 1 layout, without checking the storage version.
+	pub struct UncheckedMigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+			Kitties::<T>::translate::<OldKitty<T>, _>(|_, old| {
+				translated.saturating_inc();
+				// We have no record of when existing kitties were born, or who their parents were.
+				Some(Kitty {
+					dna: old.dna.into(),
+					owner: old.owner,
+					price: old.price,
+					parents: None,
+					generation: 0,
+					born_at: Zero::zero(),
+				})
+			});
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			// Count the raw entries, so we can check that none of them failed to translate.
+			let kitties = Kitties::<T>::iter_keys().count() as u32;
+			Ok(kitties.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let expected = u32::decode(&mut &state[..])
+				.map_err(|_| "the state from `pre_upgrade` could not be decoded")?;
+			let mut kitties = 0u32;
+			for (kitty_id, kitty) in Kitties::<T>::iter() {
+				ensure!(kitty.dna == kitty_id, "a kitty is stored under the wrong id");
+				kitties.saturating_inc();
+			}
+			ensure!(kitties == expected, "some kitties could not be translated");
+			Ok(())
+		}
+	}
+
+	/// Migrates `Kitties` from storage version 0 to 1, and then bumps the storage version.
+	pub type MigrateToV1<T> = VersionedMigration<
+		0,
+		1,
+		UncheckedMigrateToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migrates `KittiesOwned` from storage version 1 to 2.
+///
+/// Version 2 stores each kitty owned by an account as a separate entry, together with a count of
+/// the kitties owned by each account, instead of a single vector per account.
+pub mod v2 {
+	use super::*;
+
+	/// The layout of `KittiesOwned` in storage version 1.
+	///
+	/// We decode the vectors without a bound, so that no account can be lost to a lower bound.
+	#[storage_alias]
+	pub type KittiesOwned<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Vec<[u8; 32]>,
+		ValueQuery,
+	>;
+
+	/// Moves every owned kitty into the version 2 layout, without checking the storage version.
+	pub struct UncheckedMigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			// The new layout uses the same storage prefix, so we must take out all of the old
+			// vectors before writing any new entries.
+			let old: Vec<_> = KittiesOwned::<T>::drain().collect();
+			let accounts = old.len() as u64;
+			let mut writes = accounts;
+			for (owner, kitties) in old {
+				for kitty_id in &kitties {
+					crate::KittiesOwned::<T>::insert(&owner, kitty_id, ());
+				}
+				CountForKittiesOwned::<T>::insert(&owner, kitties.len() as u32);
+				writes.saturating_accrue(kitties.len() as u64 + 1);
+			}
+
+			T::DbWeight::get().reads_writes(accounts, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let owned: u32 =
+				KittiesOwned::<T>::iter_values().map(|kitties| kitties.len() as u32).sum();
+			Ok(owned.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let expected = u32::decode(&mut &state[..])
+				.map_err(|_| "the state from `pre_upgrade` could not be decoded")?;
+			let owned = crate::KittiesOwned::<T>::iter_keys().count() as u32;
+			ensure!(owned == expected, "some owned kitties were not migrated");
+			let counted: u32 = CountForKittiesOwned::<T>::iter_values().sum();
+			ensure!(counted == expected, "`CountForKittiesOwned` does not match the owned kitties");
+			Ok(())
+		}
+	}
+
+	/// Migrates `KittiesOwned` from storage version 1 to 2, and then bumps the storage version.
+	pub type MigrateToV2<T> = VersionedMigration<
+		1,
+		2,
+		UncheckedMigrateToV2<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
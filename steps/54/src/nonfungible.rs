@@ -0,0 +1,65 @@
+// Generic NFT traits for the Kitties Pallet.
+//
+// Other pallets in a runtime, like an escrow or an XCM asset transactor, should not depend on the
+// storage of our pallet to work with kitties. Instead, we implement the single collection
+// `nonfungible_v2` traits from FRAME, so any pallet which is generic over them can inspect, move,
+// mint and burn kitties as regular NFTs. The `ItemId` of a kitty is its DNA.
+//
+// Learn more about the `nonfungible_v2` traits:
+// https://paritytech.github.io/polkadot-sdk/master/frame_support/traits/tokens/nonfungible_v2/index.html
+
+use super::*;
+use frame::deps::sp_runtime::TokenError;
+use frame::traits::tokens::nonfungible_v2;
+
+impl<T: Config> nonfungible_v2::Inspect<T::AccountId> for Pallet<T> {
+	type ItemId = [u8; 32];
+
+	fn owner(item: &Self::ItemId) -> Option<T::AccountId> {
+		Kitties::<T>::get(item).map(|kitty| kitty.owner)
+	}
+
+	fn can_transfer(item: &Self::ItemId) -> bool {
+		!Paused::<T>::get().transfer &&
+			Kitties::<T>::contains_key(item) &&
+			!Auctions::<T>::contains_key(item) &&
+			!KittyLocks::<T>::contains_key(item)
+	}
+}
+
+impl<T: Config> nonfungible_v2::Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(item: &Self::ItemId, destination: &T::AccountId) -> DispatchResult {
+		// Other pallets must not be able to move kitties while users cannot.
+		ensure!(!Paused::<T>::get().transfer, Error::<T>::TransfersPaused);
+		let owner = Kitties::<T>::get(item).ok_or(Error::<T>::NoKitty)?.owner;
+		Self::do_transfer(owner, destination.clone(), *item)
+	}
+
+	fn disable_transfer(_item: &Self::ItemId) -> DispatchResult {
+		Err(TokenError::Unsupported.into())
+	}
+
+	fn enable_transfer(_item: &Self::ItemId) -> DispatchResult {
+		Err(TokenError::Unsupported.into())
+	}
+}
+
+// Kitties have no per item configuration, so the `ItemConfig` is `()`.
+impl<T: Config> nonfungible_v2::Mutate<T::AccountId, ()> for Pallet<T> {
+	fn mint_into(
+		item: &Self::ItemId,
+		who: &T::AccountId,
+		_config: &(),
+		_deposit_collection_owner: bool,
+	) -> DispatchResult {
+		Self::mint(who.clone(), *item, None)
+	}
+
+	fn burn(item: &Self::ItemId, maybe_check_owner: Option<&T::AccountId>) -> DispatchResult {
+		let owner = Kitties::<T>::get(item).ok_or(Error::<T>::NoKitty)?.owner;
+		if let Some(check_owner) = maybe_check_owner {
+			ensure!(*check_owner == owner, Error::<T>::NotOwner);
+		}
+		Self::do_burn(owner, *item)
+	}
+}
@@ -0,0 +1,43 @@
+// A call filter for pausing the Kitties Pallet.
+//
+// Each paused call already fails with an error when it is dispatched. A runtime can also use
+// `PauseFilter` in its `BaseCallFilter`, so that paused calls are rejected before they run, for
+// example when they are nested inside a batch or a proxy call. The calls of other pallets are never
+// filtered.
+//
+// The admin calls are never paused, so that the admin origin can always unpause the pallet or fix
+// the state of a kitty while everything else is frozen.
+//
+// Learn more about call filters:
+// https://paritytech.github.io/polkadot-sdk/master/frame_system/pallet/trait.Config.html#associatedtype.BaseCallFilter
+
+use super::*;
+use frame::traits::Contains;
+use frame::traits::IsSubType;
+
+pub struct PauseFilter<T>(core::marker::PhantomData<T>);
+
+impl<T: Config> PauseFilter<T> {
+	// Returns whether `call` is stopped by the current pause flags.
+	pub fn is_paused(call: &Call<T>) -> bool {
+		let paused = Paused::<T>::get();
+		match call {
+			Call::create_kitty {} | Call::breed { .. } => paused.mint,
+			Call::transfer { .. } | Call::transfer_from { .. } => paused.transfer,
+			Call::set_price { .. } |
+			Call::buy_kitty { .. } |
+			Call::start_auction { .. } |
+			Call::bid { .. } |
+			Call::start_dutch_auction { .. } |
+			Call::make_offer { .. } |
+			Call::accept_offer { .. } => paused.sale,
+			_ => false,
+		}
+	}
+}
+
+impl<T: Config, RuntimeCall: IsSubType<Call<T>>> Contains<RuntimeCall> for PauseFilter<T> {
+	fn contains(call: &RuntimeCall) -> bool {
+		call.is_sub_type().is_none_or(|call| !Self::is_paused(call))
+	}
+}
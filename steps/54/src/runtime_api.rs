@@ -0,0 +1,46 @@
+// The Runtime API for the Kitties Pallet.
+//
+// Runtime APIs let clients, like an indexer or a dapp, query the state of our pallet by calling
+// into the runtime, rather than decoding raw storage keys which break whenever the storage layout
+// changes. The runtime implements this API using the helper functions on `Pallet`.
+//
+// Learn more about Runtime APIs:
+// https://paritytech.github.io/polkadot-sdk/master/sp_api/macro.decl_runtime_apis.html
+
+use alloc::vec::Vec;
+use codec::Codec;
+use frame::deps::sp_runtime::traits::NumberFor;
+use frame::prelude::*;
+use frame::runtime::apis::decl_runtime_apis;
+
+/// A client friendly view of a `Kitty`, which does not depend on the runtime `Config`.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct KittyInfo<AccountId, Balance, BlockNumber> {
+	pub dna: [u8; 32],
+	pub owner: AccountId,
+	pub price: Option<Balance>,
+	pub parents: Option<([u8; 32], [u8; 32])>,
+	pub generation: u16,
+	pub born_at: BlockNumber,
+}
+
+decl_runtime_apis! {
+	pub trait KittiesApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Get a single kitty by its id.
+		fn kitty(id: [u8; 32]) -> Option<KittyInfo<AccountId, Balance, NumberFor<Block>>>;
+
+		/// Get the ids of all kitties owned by `owner`.
+		fn kitties_of(owner: AccountId) -> Vec<[u8; 32]>;
+
+		/// Get up to `limit` kitties which are for sale, and their price, skipping the first
+		/// `start` of them.
+		fn listed_for_sale(start: u32, limit: u32) -> Vec<([u8; 32], Balance)>;
+
+		/// Get the total number of kitties.
+		fn total_count() -> u32;
+	}
+}
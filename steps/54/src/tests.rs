@@ -14,6 +14,7 @@
 use crate as pallet_kitties;
 use crate::*;
 use frame::deps::sp_io;
+use frame::runtime::apis;
 use frame::runtime::prelude::*;
 use frame::testing_prelude::*;
 use frame::traits::fungible::*;
@@ -27,7 +28,16 @@ type Block = frame_system::mocking::MockBlock<TestRuntime>;
 // We create the constants `ALICE` and `BOB` to make it clear when we are representing users below.
 const ALICE: u64 = 1;
 const BOB: u64 = 2;
-const DEFAULT_KITTY: Kitty<TestRuntime> = Kitty { dna: [0u8; 32], owner: 0, price: None };
+const CHARLIE: u64 = 3;
+const TREASURY: u64 = 99;
+const DEFAULT_KITTY: Kitty<TestRuntime> = Kitty {
+	dna: Dna::new([0u8; 32]),
+	owner: 0,
+	price: None,
+	parents: None,
+	generation: 0,
+	born_at: 0,
+};
 
 // Our blockchain tests only need 3 Pallets:
 // 1. System: Which is included with every FRAME runtime.
@@ -47,6 +57,7 @@ construct_runtime! {
 impl frame_system::Config for TestRuntime {
 	type Block = Block;
 	type AccountData = pallet_balances::AccountData<Balance>;
+	type BaseCallFilter = PauseFilter<TestRuntime>;
 }
 
 // Normally `pallet_balances` would have many more configurations, but you can see that we use some
@@ -55,6 +66,8 @@ impl frame_system::Config for TestRuntime {
 impl pallet_balances::Config for TestRuntime {
 	type AccountStore = System;
 	type Balance = Balance;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type ExistentialDeposit = ExistentialDeposit;
 }
 
 // This is the configuration of our Pallet! If you make changes to the pallet's `trait Config`, you
@@ -62,17 +75,114 @@ impl pallet_balances::Config for TestRuntime {
 impl pallet_kitties::Config for TestRuntime {
 	type RuntimeEvent = RuntimeEvent;
 	type NativeBalance = PalletBalances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WeightInfo = ();
+	type MaxKittiesOwned = MaxKittiesOwned;
+	type BreedingCooldownBase = ConstU64<10>;
+	type BreedingCooldownFactor = ConstU32<2>;
+	type MaxBreedingCooldown = ConstU64<100>;
+	type MaxAuctionDuration = ConstU64<100>;
+	type MaxAuctionsPerBlock = ConstU32<2>;
+	type MaxOffersPerKitty = ConstU32<2>;
+	type MaxOffersPerBlock = ConstU32<2>;
+	type MinOfferAmount = ConstU64<10>;
+	type MaxOfferDuration = ConstU64<100>;
+	type RoyaltyPercent = RoyaltyPercent;
+	type MarketplaceFee = MarketplaceFee;
+	type FeeDestination = FeeDestination;
+	type KittyDeposit = KittyDeposit;
+	type MintBeneficiary = FeeDestination;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+}
+
+parameter_types! {
+	// Tests can raise this to check what happens to payments below it.
+	pub storage ExistentialDeposit: Balance = 1;
+	// Tests can lower this to check what happens to accounts over the bound.
+	pub storage MaxKittiesOwned: u32 = 100;
+	pub const RoyaltyPercent: Permill = Permill::from_percent(10);
+	// The fee is zero unless a test sets it, so that other tests are not affected.
+	pub storage MarketplaceFee: Permill = Permill::zero();
+	pub const FeeDestination: u64 = TREASURY;
+	// The deposit is zero unless a test sets it, so that kitties can be minted without funds.
+	pub storage KittyDeposit: u64 = 0;
+}
+
+// This is how a real runtime would expose our Runtime API to clients. We implement it for our
+// `TestRuntime` so that we can test the API implementation directly.
+// Every runtime must also implement the `Core` API, but our tests never execute whole blocks, so
+// calling anything but `version` is a bug.
+impl_runtime_apis! {
+	impl apis::Core<Block> for TestRuntime {
+		fn version() -> RuntimeVersion {
+			RuntimeVersion::default()
+		}
+
+		fn execute_block(_block: Block) {
+			unreachable!("not used in tests")
+		}
+
+		fn initialize_block(_header: &HeaderFor<TestRuntime>) -> ExtrinsicInclusionMode {
+			unreachable!("not used in tests")
+		}
+	}
+
+	impl runtime_api::KittiesApi<Block, u64, Balance> for TestRuntime {
+		fn kitty(id: [u8; 32]) -> Option<runtime_api::KittyInfo<u64, Balance, u64>> {
+			PalletKitties::kitty(id)
+		}
+
+		fn kitties_of(owner: u64) -> Vec<[u8; 32]> {
+			PalletKitties::kitties_of(owner)
+		}
+
+		fn listed_for_sale(start: u32, limit: u32) -> Vec<([u8; 32], Balance)> {
+			PalletKitties::listed_for_sale(start, limit)
+		}
+
+		fn total_count() -> u32 {
+			PalletKitties::total_count()
+		}
+	}
 }
 
 // We need to run most of our tests using this function: `new_test_ext().execute_with(|| { ... });`
 // It simulates the blockchain database backend for our tests.
 // If you forget to include this and try to access your Pallet storage, you will get an error like:
 // "`get_version_1` called outside of an Externalities-provided environment."
-pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::<TestRuntime>::default()
-		.build_storage()
-		.unwrap()
-		.into()
+pub fn new_test_ext() -> TestExt {
+	new_test_ext_with_kitties(vec![])
+}
+
+// Like `new_test_ext`, but the given kitties already exist at genesis, as `(owner, dna, price)`.
+pub fn new_test_ext_with_kitties(kitties: Vec<(u64, [u8; 32], Option<Balance>)>) -> TestExt {
+	let storage = RuntimeGenesisConfig {
+		pallet_kitties: pallet_kitties::GenesisConfig { kitties },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	TestExt(storage.into())
+}
+
+// Wraps the test externalities, so that every test also checks that our storage is still
+// consistent when it finishes.
+pub struct TestExt(sp_io::TestExternalities);
+
+impl TestExt {
+	pub fn execute_with<R>(&mut self, test: impl FnOnce() -> R) -> R {
+		self.0.execute_with(|| {
+			let result = test();
+			PalletKitties::do_try_state().expect("storage should be consistent after every test");
+			result
+		})
+	}
+}
+
+// Moves the chain to block `n`, running the `on_initialize` hook of our pallet like a real block.
+pub fn run_to_block(n: u64) {
+	System::set_block_number(n);
+	PalletKitties::on_initialize(n);
 }
 
 #[test]
@@ -129,6 +239,8 @@ fn count_for_kitties_created_correctly() {
 		CountForKitties::<TestRuntime>::set(1337u32);
 		// You can `put` the value directly with a `u32`.
 		CountForKitties::<TestRuntime>::put(1337u32);
+		// There are no kitties, so we reset the count to leave storage consistent.
+		CountForKitties::<TestRuntime>::kill();
 	})
 }
 
@@ -154,6 +266,7 @@ fn mint_errors_when_overflow() {
 			PalletKitties::create_kitty(RuntimeOrigin::signed(1)),
 			Error::<TestRuntime>::TooManyKitties
 		);
+		CountForKitties::<TestRuntime>::kill();
 	})
 }
 
@@ -164,6 +277,8 @@ fn kitties_map_created_correctly() {
 		assert!(!Kitties::<TestRuntime>::contains_key(zero_key));
 		Kitties::<TestRuntime>::insert(zero_key, DEFAULT_KITTY);
 		assert!(Kitties::<TestRuntime>::contains_key(zero_key));
+		// The kitty has no owner, so we remove it to leave storage consistent.
+		Kitties::<TestRuntime>::remove(zero_key);
 	})
 }
 
@@ -178,8 +293,11 @@ fn create_kitty_adds_to_map() {
 #[test]
 fn cannot_mint_duplicate_kitty() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(PalletKitties::mint(ALICE, [0u8; 32]));
-		assert_noop!(PalletKitties::mint(BOB, [0u8; 32]), Error::<TestRuntime>::DuplicateKitty);
+		assert_ok!(PalletKitties::mint(ALICE, [0u8; 32], None));
+		assert_noop!(
+			PalletKitties::mint(BOB, [0u8; 32], None),
+			Error::<TestRuntime>::DuplicateKitty
+		);
 	})
 }
 
@@ -197,7 +315,7 @@ fn kitty_struct_has_expected_traits() {
 #[test]
 fn mint_stores_owner_in_kitty() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(PalletKitties::mint(1337, [42u8; 32]));
+		assert_ok!(PalletKitties::mint(1337, [42u8; 32], None));
 		let kitty = Kitties::<TestRuntime>::get([42u8; 32]).unwrap();
 		assert_eq!(kitty.owner, 1337);
 		assert_eq!(kitty.dna, [42u8; 32]);
@@ -220,20 +338,19 @@ fn create_kitty_makes_unique_kitties() {
 fn kitties_owned_created_correctly() {
 	new_test_ext().execute_with(|| {
 		// Initially users have no kitties owned.
-		assert_eq!(KittiesOwned::<TestRuntime>::get(1).len(), 0);
+		assert_eq!(CountForKittiesOwned::<TestRuntime>::get(1), 0);
 		// Let's create two kitties.
 		assert_ok!(PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)));
 		assert_ok!(PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)));
 		// Now they should have two kitties owned.
-		assert_eq!(KittiesOwned::<TestRuntime>::get(1).len(), 2);
+		assert_eq!(CountForKittiesOwned::<TestRuntime>::get(1), 2);
 	});
 }
 
 #[test]
 fn cannot_own_too_many_kitties() {
 	new_test_ext().execute_with(|| {
-		// If your max owned is different than 100, you will need to update this.
-		for _ in 0..100 {
+		for _ in 0..MaxKittiesOwned::get() {
 			assert_ok!(PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)));
 		}
 		assert_noop!(
@@ -243,6 +360,45 @@ fn cannot_own_too_many_kitties() {
 	});
 }
 
+#[test]
+fn max_kitties_owned_is_configurable() {
+	new_test_ext().execute_with(|| {
+		MaxKittiesOwned::set(&2);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert_noop!(
+			PalletKitties::mint(ALICE, [3u8; 32], None),
+			Error::<TestRuntime>::TooManyOwned
+		);
+		assert_ok!(PalletKitties::mint(BOB, [3u8; 32], None));
+		assert_noop!(
+			PalletKitties::transfer(RuntimeOrigin::signed(BOB), ALICE, [3u8; 32]),
+			Error::<TestRuntime>::TooManyOwned
+		);
+	})
+}
+
+#[test]
+fn lowering_max_kitties_owned_keeps_existing_kitties() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [3u8; 32], None));
+		assert_ok!(PalletKitties::mint(BOB, [4u8; 32], None));
+
+		// Alice keeps all of her kitties, but cannot get any more.
+		MaxKittiesOwned::set(&2);
+		assert_eq!(PalletKitties::kitties_of(ALICE).len(), 3);
+		assert_noop!(
+			PalletKitties::transfer(RuntimeOrigin::signed(BOB), ALICE, [4u8; 32]),
+			Error::<TestRuntime>::TooManyOwned
+		);
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [3u8; 32]));
+		assert_eq!(CountForKittiesOwned::<TestRuntime>::get(ALICE), 2);
+		assert_eq!(CountForKittiesOwned::<TestRuntime>::get(BOB), 2);
+	})
+}
+
 #[test]
 fn transfer_emits_event() {
 	new_test_ext().execute_with(|| {
@@ -265,10 +421,10 @@ fn transfer_logic_works() {
 		assert_ok!(PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)));
 		// Starting state looks good.
 		let kitty = &Kitties::<TestRuntime>::iter_values().collect::<Vec<_>>()[0];
-		let kitty_id = kitty.dna;
+		let kitty_id = *kitty.dna.as_bytes();
 		assert_eq!(kitty.owner, ALICE);
-		assert_eq!(KittiesOwned::<TestRuntime>::get(ALICE), vec![kitty_id]);
-		assert_eq!(KittiesOwned::<TestRuntime>::get(BOB), vec![]);
+		assert_eq!(PalletKitties::kitties_of(ALICE), vec![kitty_id]);
+		assert!(PalletKitties::kitties_of(BOB).is_empty());
 		// Cannot transfer to yourself.
 		assert_noop!(
 			PalletKitties::transfer(RuntimeOrigin::signed(ALICE), ALICE, kitty_id),
@@ -287,8 +443,11 @@ fn transfer_logic_works() {
 		// Transfer should work when parameters are right.
 		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, kitty_id));
 		// Storage is updated correctly.
-		assert_eq!(KittiesOwned::<TestRuntime>::get(ALICE), vec![]);
-		assert_eq!(KittiesOwned::<TestRuntime>::get(BOB), vec![kitty_id]);
+		assert!(PalletKitties::kitties_of(ALICE).is_empty());
+		assert_eq!(PalletKitties::kitties_of(BOB), vec![kitty_id]);
+		// An account which owns no kitties has no count stored.
+		assert!(!CountForKittiesOwned::<TestRuntime>::contains_key(ALICE));
+		assert_eq!(CountForKittiesOwned::<TestRuntime>::get(BOB), 1);
 		let kitty = &Kitties::<TestRuntime>::iter_values().collect::<Vec<_>>()[0];
 		assert_eq!(kitty.owner, BOB);
 	});
@@ -332,7 +491,7 @@ fn set_price_logic_works() {
 		assert_ok!(PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)));
 		let kitty = &Kitties::<TestRuntime>::iter_values().collect::<Vec<_>>()[0];
 		assert_eq!(kitty.price, None);
-		let kitty_id = kitty.dna;
+		let kitty_id = *kitty.dna.as_bytes();
 		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(ALICE), kitty_id, Some(1337)));
 		let kitty = Kitties::<TestRuntime>::get(kitty_id).unwrap();
 		assert_eq!(kitty.price, Some(1337));
@@ -351,7 +510,8 @@ fn do_buy_kitty_emits_event() {
 		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), kitty_id, 1337));
 		// Assert the last event by our blockchain is the `Created` event with the correct owner.
 		System::assert_last_event(
-			Event::<TestRuntime>::Sold { buyer: BOB, kitty_id, price: 1337 }.into(),
+			Event::<TestRuntime>::Sold { buyer: BOB, kitty_id, price: 1337, royalty: 0, fee: 0 }
+				.into(),
 		);
 	})
 }
@@ -361,9 +521,9 @@ fn do_buy_kitty_logic_works() {
 	new_test_ext().execute_with(|| {
 		assert_ok!(PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)));
 		let kitty = &Kitties::<TestRuntime>::iter_values().collect::<Vec<_>>()[0];
-		let kitty_id = kitty.dna;
+		let kitty_id = *kitty.dna.as_bytes();
 		assert_eq!(kitty.owner, ALICE);
-		assert_eq!(KittiesOwned::<TestRuntime>::get(ALICE), vec![kitty_id]);
+		assert_eq!(PalletKitties::kitties_of(ALICE), vec![kitty_id]);
 		// Cannot buy kitty which does not exist.
 		assert_noop!(
 			PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [0u8; 32], 1337),
@@ -387,15 +547,15 @@ fn do_buy_kitty_logic_works() {
 		);
 		// Cannot buy kitty if it would kill your account (i.e. set your balance to 0).
 		assert_ok!(PalletBalances::mint_into(&BOB, 1337));
-		assert!(
-			PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), kitty_id, 1337).is_err(),
-			// TODO: assert_noop on DispatchError::Token(TokenError::NotExpendable)
+		assert_noop!(
+			PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), kitty_id, 1337),
+			frame::deps::sp_runtime::TokenError::NotExpendable
 		);
 		// When everything is right, it works.
 		assert_ok!(PalletBalances::mint_into(&BOB, 100_000));
 		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), kitty_id, 1337));
 		// State is updated correctly.
-		assert_eq!(KittiesOwned::<TestRuntime>::get(BOB), vec![kitty_id]);
+		assert_eq!(PalletKitties::kitties_of(BOB), vec![kitty_id]);
 		let kitty = Kitties::<TestRuntime>::get(kitty_id).unwrap();
 		assert_eq!(kitty.owner, BOB);
 		// Price is reset to `None`.
@@ -405,3 +565,2223 @@ fn do_buy_kitty_logic_works() {
 		assert_eq!(PalletBalances::balance(&BOB), 100_000);
 	})
 }
+
+#[test]
+fn breed_emits_event() {
+	new_test_ext().execute_with(|| {
+		// We need to set block number to 1 to view events.
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		// The child DNA depends on the current block state, so compute it before breeding.
+		let child = PalletKitties::breed_dna(&[1u8; 32], &[2u8; 32]);
+		assert_ok!(PalletKitties::breed(RuntimeOrigin::signed(ALICE), [1u8; 32], [2u8; 32]));
+		System::assert_last_event(
+			Event::<TestRuntime>::Bred { owner: ALICE, parents: ([1u8; 32], [2u8; 32]), child }
+				.into(),
+		);
+	})
+}
+
+#[test]
+fn breed_logic_works() {
+	new_test_ext().execute_with(|| {
+		let dna_1 = [0b1010_1010u8; 32];
+		let dna_2 = [0b0101_0101u8; 32];
+		assert_ok!(PalletKitties::mint(ALICE, dna_1, None));
+		assert_ok!(PalletKitties::mint(ALICE, dna_2, None));
+		assert_ok!(PalletKitties::mint(BOB, [3u8; 32], None));
+		// Cannot breed a kitty with itself.
+		assert_noop!(
+			PalletKitties::breed(RuntimeOrigin::signed(ALICE), dna_1, dna_1),
+			Error::<TestRuntime>::BreedWithSelf
+		);
+		// Cannot breed a kitty which does not exist.
+		assert_noop!(
+			PalletKitties::breed(RuntimeOrigin::signed(ALICE), dna_1, [0u8; 32]),
+			Error::<TestRuntime>::NoKitty
+		);
+		// Cannot breed with a kitty you do not own.
+		assert_noop!(
+			PalletKitties::breed(RuntimeOrigin::signed(ALICE), dna_1, [3u8; 32]),
+			Error::<TestRuntime>::NotOwner
+		);
+		// Breeding works when both parents are owned by the caller.
+		assert_ok!(PalletKitties::breed(RuntimeOrigin::signed(ALICE), dna_1, dna_2));
+		assert_eq!(CountForKitties::<TestRuntime>::get(), 4);
+		assert_eq!(CountForKittiesOwned::<TestRuntime>::get(ALICE), 3);
+		let child = PalletKitties::kitties_of(ALICE)
+			.into_iter()
+			.find(|id| ![dna_1, dna_2].contains(id))
+			.unwrap();
+		let kitty = Kitties::<TestRuntime>::get(child).unwrap();
+		assert_eq!(kitty.owner, ALICE);
+		// Every bit of the child DNA comes from one of the parents.
+		for i in 0..32 {
+			assert_eq!((child[i] ^ dna_1[i]) & (child[i] ^ dna_2[i]), 0);
+		}
+	})
+}
+
+#[test]
+fn breed_dna_is_deterministic() {
+	new_test_ext().execute_with(|| {
+		let dna_1 = [0xFFu8; 32];
+		let dna_2 = [0x00u8; 32];
+		// Within the same block and extrinsic, the same parents always produce the same child.
+		let child = PalletKitties::breed_dna(&dna_1, &dna_2);
+		assert_eq!(child, PalletKitties::breed_dna(&dna_1, &dna_2));
+		// With these parents, the child is exactly the entropy from `gen_dna`.
+		assert_eq!(child, PalletKitties::gen_dna());
+	})
+}
+
+#[test]
+fn mint_stores_lineage_in_kitty() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		let kitty = Kitties::<TestRuntime>::get([1u8; 32]).unwrap();
+		assert_eq!(kitty.parents, None);
+		assert_eq!(kitty.generation, 0);
+		assert_eq!(kitty.born_at, 5);
+		// Children must have existing parents.
+		assert_noop!(
+			PalletKitties::mint(ALICE, [2u8; 32], Some(([1u8; 32], [0u8; 32]))),
+			Error::<TestRuntime>::NoKitty
+		);
+	})
+}
+
+#[test]
+fn breed_tracks_generation() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert_ok!(PalletKitties::breed(RuntimeOrigin::signed(ALICE), [1u8; 32], [2u8; 32]));
+		let child_id = PalletKitties::kitties_of(ALICE)
+			.into_iter()
+			.find(|id| ![[1u8; 32], [2u8; 32]].contains(id))
+			.unwrap();
+		let child = Kitties::<TestRuntime>::get(child_id).unwrap();
+		assert_eq!(child.parents, Some(([1u8; 32], [2u8; 32])));
+		assert_eq!(child.generation, 1);
+		assert_eq!(child.born_at, 1);
+		// Breeding a generation `1` kitty with a generation `0` kitty results in generation `2`.
+		System::set_block_number(100);
+		assert_ok!(PalletKitties::breed(RuntimeOrigin::signed(ALICE), child_id, [1u8; 32]));
+		let grandchild_id = PalletKitties::kitties_of(ALICE)
+			.into_iter()
+			.find(|id| ![[1u8; 32], [2u8; 32], child_id].contains(id))
+			.unwrap();
+		let grandchild = Kitties::<TestRuntime>::get(grandchild_id).unwrap();
+		assert_eq!(grandchild.parents, Some((child_id, [1u8; 32])));
+		assert_eq!(grandchild.generation, 2);
+		assert_eq!(grandchild.born_at, 100);
+	})
+}
+
+#[test]
+fn kitty_max_encoded_len_includes_lineage() {
+	// dna (32) + owner (8) + price (1 + 8) + parents (1 + 64) + generation (2) + born_at (8)
+	assert_eq!(Kitty::<TestRuntime>::max_encoded_len(), 124);
+}
+
+#[test]
+fn genesis_config_mints_kitties() {
+	new_test_ext_with_kitties(vec![
+		(ALICE, [1u8; 32], None),
+		(ALICE, [2u8; 32], Some(10)),
+		(BOB, [3u8; 32], Some(20)),
+	])
+	.execute_with(|| {
+		assert_eq!(CountForKitties::<TestRuntime>::get(), 3);
+		let mut owned = PalletKitties::kitties_of(ALICE);
+		owned.sort();
+		assert_eq!(owned, vec![[1u8; 32], [2u8; 32]]);
+		assert_eq!(PalletKitties::kitties_of(BOB), vec![[3u8; 32]]);
+		assert_eq!(CountForKittiesOwned::<TestRuntime>::get(ALICE), 2);
+		let kitty = Kitties::<TestRuntime>::get([3u8; 32]).unwrap();
+		assert_eq!(kitty.owner, BOB);
+		assert_eq!(kitty.price, Some(20));
+		assert_eq!(kitty.born_at, 0);
+		assert_eq!(Creators::<TestRuntime>::get([3u8; 32]), Some(BOB));
+		let mut listed = PalletKitties::listed_for_sale(0, 10);
+		listed.sort();
+		assert_eq!(listed, vec![([2u8; 32], 10), ([3u8; 32], 20)]);
+	})
+}
+
+#[test]
+#[should_panic(expected = "genesis kitties must be unique")]
+fn genesis_config_rejects_duplicate_kitties() {
+	new_test_ext_with_kitties(vec![(ALICE, [1u8; 32], None), (BOB, [1u8; 32], None)]);
+}
+
+#[test]
+fn genesis_kitties_can_be_bought() {
+	new_test_ext_with_kitties(vec![(ALICE, [1u8; 32], Some(10))]).execute_with(|| {
+		assert_ok!(PalletBalances::mint_into(&ALICE, 100));
+		assert_ok!(PalletBalances::mint_into(&BOB, 100));
+		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [1u8; 32], 10));
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, BOB);
+	})
+}
+
+#[test]
+fn try_state_detects_inconsistent_ownership() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(BOB, [2u8; 32], None));
+		assert_ok!(PalletKitties::do_try_state());
+
+		// A kitty which is not counted.
+		CountForKitties::<TestRuntime>::set(1);
+		assert_eq!(
+			PalletKitties::do_try_state(),
+			Err("`CountForKitties` does not match the number of kitties".into())
+		);
+		CountForKitties::<TestRuntime>::set(2);
+
+		// A kitty which is missing from the kitties owned by its owner.
+		KittiesOwned::<TestRuntime>::remove(BOB, [2u8; 32]);
+		assert_eq!(
+			PalletKitties::do_try_state(),
+			Err("a kitty is missing from the kitties owned by its owner".into())
+		);
+		KittiesOwned::<TestRuntime>::insert(BOB, [2u8; 32], ());
+
+		// A kitty which is owned by two accounts.
+		KittiesOwned::<TestRuntime>::insert(BOB, [1u8; 32], ());
+		CountForKittiesOwned::<TestRuntime>::insert(BOB, 2);
+		assert_eq!(
+			PalletKitties::do_try_state(),
+			Err("an owner has a kitty which belongs to someone else".into())
+		);
+		KittiesOwned::<TestRuntime>::remove(BOB, [1u8; 32]);
+		CountForKittiesOwned::<TestRuntime>::insert(BOB, 1);
+
+		// A kitty which does not exist.
+		KittiesOwned::<TestRuntime>::insert(BOB, [3u8; 32], ());
+		CountForKittiesOwned::<TestRuntime>::insert(BOB, 2);
+		assert_eq!(
+			PalletKitties::do_try_state(),
+			Err("an owner has a kitty which does not exist".into())
+		);
+		KittiesOwned::<TestRuntime>::remove(BOB, [3u8; 32]);
+
+		// A count which does not match the kitties owned.
+		assert_eq!(
+			PalletKitties::do_try_state(),
+			Err("`CountForKittiesOwned` does not match the kitties owned by an account".into())
+		);
+		CountForKittiesOwned::<TestRuntime>::insert(BOB, 1);
+		assert_ok!(PalletKitties::do_try_state());
+	})
+}
+
+#[test]
+fn migrate_to_v1_works() {
+	use crate::migrations::v1::MigrateToV1;
+	use crate::migrations::v1::OldKitty;
+	use crate::migrations::v2::MigrateToV2;
+	use frame::deps::frame_support::storage::unhashed;
+	use frame::traits::OnRuntimeUpgrade;
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<PalletKitties>();
+		let old_kitty = OldKitty::<TestRuntime> { dna: [1u8; 32], owner: ALICE, price: Some(10) };
+		unhashed::put(&Kitties::<TestRuntime>::hashed_key_for([1u8; 32]), &old_kitty);
+
+		let old_kitty = OldKitty::<TestRuntime> { dna: [2u8; 32], owner: BOB, price: None };
+		unhashed::put(&Kitties::<TestRuntime>::hashed_key_for([2u8; 32]), &old_kitty);
+		migrations::v2::KittiesOwned::<TestRuntime>::insert(ALICE, vec![[1u8; 32]]);
+		migrations::v2::KittiesOwned::<TestRuntime>::insert(BOB, vec![[2u8; 32]]);
+		CountForKitties::<TestRuntime>::set(2);
+
+		MigrateToV1::<TestRuntime>::on_runtime_upgrade();
+
+		let kitty = Kitties::<TestRuntime>::get([1u8; 32]).unwrap();
+		assert_eq!(kitty.dna, [1u8; 32]);
+		assert_eq!(kitty.owner, ALICE);
+		assert_eq!(kitty.price, Some(10));
+		assert_eq!(kitty.parents, None);
+		assert_eq!(kitty.generation, 0);
+		assert_eq!(kitty.born_at, 0);
+		let kitty = Kitties::<TestRuntime>::get([2u8; 32]).unwrap();
+		assert_eq!(kitty.owner, BOB);
+		assert_eq!(kitty.price, None);
+		assert_eq!(PalletKitties::on_chain_storage_version(), 1);
+
+		// Finish upgrading, so our storage is in the latest layout.
+		MigrateToV2::<TestRuntime>::on_runtime_upgrade();
+	})
+}
+
+#[test]
+fn migrate_to_v1_only_runs_once() {
+	use crate::migrations::v1::MigrateToV1;
+	use frame::deps::frame_support::storage::unhashed;
+	use frame::traits::OnRuntimeUpgrade;
+
+	new_test_ext().execute_with(|| {
+		// Kitties which are already in the new layout must not be translated again.
+		StorageVersion::new(1).put::<PalletKitties>();
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		let key = Kitties::<TestRuntime>::hashed_key_for([1u8; 32]);
+		let raw_kitty = unhashed::get_raw(&key);
+
+		MigrateToV1::<TestRuntime>::on_runtime_upgrade();
+
+		assert_eq!(unhashed::get_raw(&key), raw_kitty);
+		assert_eq!(PalletKitties::on_chain_storage_version(), 1);
+	})
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn migrate_to_v1_passes_try_runtime_checks() {
+	use crate::migrations::v1::MigrateToV1;
+	use crate::migrations::v1::OldKitty;
+	use crate::migrations::v2::MigrateToV2;
+	use frame::deps::frame_support::storage::unhashed;
+	use frame::traits::OnRuntimeUpgrade;
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<PalletKitties>();
+		for dna in [[1u8; 32], [2u8; 32]] {
+			let old_kitty = OldKitty::<TestRuntime> { dna, owner: ALICE, price: None };
+			unhashed::put(&Kitties::<TestRuntime>::hashed_key_for(dna), &old_kitty);
+		}
+		migrations::v2::KittiesOwned::<TestRuntime>::insert(ALICE, vec![[1u8; 32], [2u8; 32]]);
+		CountForKitties::<TestRuntime>::set(2);
+
+		// Runtimes run all of the migrations which are newer than the on-chain storage version.
+		assert_ok!(<(MigrateToV1<TestRuntime>, MigrateToV2<TestRuntime>)>::try_on_runtime_upgrade(
+			true
+		));
+		assert_eq!(PalletKitties::on_chain_storage_version(), 2);
+	})
+}
+
+#[test]
+fn migrate_to_v2_works() {
+	use crate::migrations::v2::MigrateToV2;
+	use frame::traits::OnRuntimeUpgrade;
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<PalletKitties>();
+		for (owner, dna) in [(ALICE, [1u8; 32]), (ALICE, [2u8; 32]), (BOB, [3u8; 32])] {
+			Kitties::<TestRuntime>::insert(dna, Kitty { dna: dna.into(), owner, ..DEFAULT_KITTY });
+		}
+		migrations::v2::KittiesOwned::<TestRuntime>::insert(ALICE, vec![[1u8; 32], [2u8; 32]]);
+		migrations::v2::KittiesOwned::<TestRuntime>::insert(BOB, vec![[3u8; 32]]);
+		CountForKitties::<TestRuntime>::set(3);
+
+		MigrateToV2::<TestRuntime>::on_runtime_upgrade();
+
+		assert!(KittiesOwned::<TestRuntime>::contains_key(ALICE, [1u8; 32]));
+		assert!(KittiesOwned::<TestRuntime>::contains_key(ALICE, [2u8; 32]));
+		assert!(KittiesOwned::<TestRuntime>::contains_key(BOB, [3u8; 32]));
+		assert_eq!(CountForKittiesOwned::<TestRuntime>::get(ALICE), 2);
+		assert_eq!(CountForKittiesOwned::<TestRuntime>::get(BOB), 1);
+		assert_eq!(PalletKitties::on_chain_storage_version(), 2);
+
+		// Kitties can be moved around as usual after the migration.
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]));
+		assert_eq!(CountForKittiesOwned::<TestRuntime>::get(BOB), 2);
+	})
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn migrate_to_v2_passes_try_runtime_checks() {
+	use crate::migrations::v2::MigrateToV2;
+	use frame::traits::OnRuntimeUpgrade;
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<PalletKitties>();
+		for dna in [[1u8; 32], [2u8; 32]] {
+			Kitties::<TestRuntime>::insert(
+				dna,
+				Kitty { dna: dna.into(), owner: ALICE, ..DEFAULT_KITTY },
+			);
+		}
+		migrations::v2::KittiesOwned::<TestRuntime>::insert(ALICE, vec![[1u8; 32], [2u8; 32]]);
+		CountForKitties::<TestRuntime>::set(2);
+
+		assert_ok!(MigrateToV2::<TestRuntime>::try_on_runtime_upgrade(true));
+		assert_eq!(PalletKitties::on_chain_storage_version(), 2);
+	})
+}
+
+#[test]
+fn breeding_cooldown_grows_with_fatigue() {
+	// The cooldown starts at the base, doubles with every breeding, and is capped at the max.
+	assert_eq!(PalletKitties::breeding_cooldown(0), 10);
+	assert_eq!(PalletKitties::breeding_cooldown(1), 20);
+	assert_eq!(PalletKitties::breeding_cooldown(3), 80);
+	assert_eq!(PalletKitties::breeding_cooldown(4), 100);
+	assert_eq!(PalletKitties::breeding_cooldown(u32::MAX), 100);
+}
+
+#[test]
+fn breed_respects_cooldown() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [3u8; 32], None));
+		assert_ok!(PalletKitties::breed(RuntimeOrigin::signed(ALICE), [1u8; 32], [2u8; 32]));
+		// Both parents are now resting.
+		let status = BreedingCooldowns::<TestRuntime>::get([1u8; 32]);
+		assert_eq!(status.times_bred, 1);
+		assert_eq!(status.ready_at, 11);
+		assert_eq!(BreedingCooldowns::<TestRuntime>::get([2u8; 32]).ready_at, 11);
+		// Neither parent can breed again until the cooldown passes.
+		System::set_block_number(10);
+		assert_noop!(
+			PalletKitties::breed(RuntimeOrigin::signed(ALICE), [1u8; 32], [3u8; 32]),
+			Error::<TestRuntime>::KittyOnCooldown
+		);
+		assert_noop!(
+			PalletKitties::breed(RuntimeOrigin::signed(ALICE), [3u8; 32], [2u8; 32]),
+			Error::<TestRuntime>::KittyOnCooldown
+		);
+		// Once it has passed, breeding works and the next cooldown is longer.
+		System::set_block_number(11);
+		assert_ok!(PalletKitties::breed(RuntimeOrigin::signed(ALICE), [1u8; 32], [3u8; 32]));
+		let status = BreedingCooldowns::<TestRuntime>::get([1u8; 32]);
+		assert_eq!(status.times_bred, 2);
+		assert_eq!(status.ready_at, 31);
+		// The other parent was bred for the first time.
+		assert_eq!(BreedingCooldowns::<TestRuntime>::get([3u8; 32]).ready_at, 21);
+	})
+}
+
+#[test]
+fn dna_encodes_like_raw_bytes() {
+	let bytes = [7u8; 32];
+	let dna = Dna::from(bytes);
+	assert_eq!(dna.encode(), bytes.encode());
+	assert_eq!(Dna::decode(&mut &bytes.encode()[..]).unwrap(), dna);
+	assert_eq!(Dna::max_encoded_len(), 32);
+}
+
+#[test]
+fn dna_decodes_genes() {
+	use crate::dna::*;
+
+	let mut bytes = [0u8; 32];
+	bytes[FUR_COLOUR_GENE] = 10;
+	bytes[EYE_COLOUR_GENE] = 3;
+	bytes[EYE_SHAPE_GENE] = 5;
+	bytes[PATTERN_GENE] = 7;
+	bytes[RARITY_GENE] = 255;
+	let dna = Dna::new(bytes);
+	assert_eq!(dna.fur_colour(), FurColour::Ginger);
+	assert_eq!(dna.eye_colour(), EyeColour::Copper);
+	assert_eq!(dna.eye_shape(), EyeShape::Almond);
+	assert_eq!(dna.pattern(), Pattern::Striped);
+	assert_eq!(dna.rarity(), Rarity::Legendary);
+	// Rarity tiers are decided by ranges of the gene.
+	assert_eq!(Rarity::from_gene(0), Rarity::Common);
+	assert_eq!(Rarity::from_gene(159), Rarity::Common);
+	assert_eq!(Rarity::from_gene(160), Rarity::Uncommon);
+	assert_eq!(Rarity::from_gene(220), Rarity::Rare);
+	assert_eq!(Rarity::from_gene(250), Rarity::Epic);
+	assert_eq!(Rarity::from_gene(254), Rarity::Epic);
+}
+
+#[test]
+fn dna_type_info_describes_genes() {
+	use frame::deps::scale_info::TypeDef;
+
+	let info = Dna::type_info();
+	let TypeDef::Composite(composite) = info.type_def else {
+		panic!("`Dna` should be a composite")
+	};
+	let names = composite.fields.iter().map(|field| field.name.unwrap()).collect::<Vec<_>>();
+	assert_eq!(
+		names,
+		vec!["fur_colour", "eye_colour", "eye_shape", "pattern", "rarity", "reserved"]
+	);
+}
+
+#[test]
+fn kitties_api_works() {
+	// This is the trait which `impl_runtime_apis!` implements for `TestRuntime`.
+	use runtime_api::runtime_decl_for_kitties_api::KittiesApiV1;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(TestRuntime::total_count(), 0);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert_ok!(PalletKitties::mint(BOB, [3u8; 32], None));
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(ALICE), [1u8; 32], Some(10)));
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(BOB), [3u8; 32], Some(30)));
+
+		assert_eq!(TestRuntime::total_count(), 3);
+		assert_eq!(
+			TestRuntime::kitty([1u8; 32]),
+			Some(runtime_api::KittyInfo {
+				dna: [1u8; 32],
+				owner: ALICE,
+				price: Some(10),
+				parents: None,
+				generation: 0,
+				born_at: 1,
+			})
+		);
+		assert_eq!(TestRuntime::kitty([0u8; 32]), None);
+		assert_eq!(TestRuntime::kitties_of(ALICE), vec![[1u8; 32], [2u8; 32]]);
+		assert!(TestRuntime::kitties_of(3).is_empty());
+
+		// Only listed kitties are returned, and the results can be paged through.
+		let mut listed = TestRuntime::listed_for_sale(0, 10);
+		listed.sort();
+		assert_eq!(listed, vec![([1u8; 32], 10), ([3u8; 32], 30)]);
+		let first = TestRuntime::listed_for_sale(0, 1);
+		let second = TestRuntime::listed_for_sale(1, 1);
+		assert_eq!(first.len(), 1);
+		assert_eq!(second.len(), 1);
+		assert_ne!(first, second);
+		assert!(TestRuntime::listed_for_sale(2, 1).is_empty());
+	})
+}
+
+#[test]
+fn kitties_api_returns_dutch_auction_price() {
+	use runtime_api::runtime_decl_for_kitties_api::KittiesApiV1;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::start_dutch_auction(
+			RuntimeOrigin::signed(ALICE),
+			[1u8; 32],
+			100,
+			10,
+			10
+		));
+		assert_eq!(TestRuntime::kitty([1u8; 32]).unwrap().price, Some(100));
+		// Halfway through the auction, the kitty and the listing both report the decayed price.
+		System::set_block_number(6);
+		assert_eq!(TestRuntime::kitty([1u8; 32]).unwrap().price, Some(55));
+		assert_eq!(TestRuntime::listed_for_sale(0, 10), vec![([1u8; 32], 55)]);
+	})
+}
+
+#[test]
+fn start_auction_ends_dutch_auction() {
+	use runtime_api::runtime_decl_for_kitties_api::KittiesApiV1;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::start_dutch_auction(
+			RuntimeOrigin::signed(ALICE),
+			[1u8; 32],
+			100,
+			10,
+			10
+		));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 10, 10));
+		assert!(!DutchAuctions::<TestRuntime>::contains_key([1u8; 32]));
+		assert_eq!(TestRuntime::kitty([1u8; 32]).unwrap().price, None);
+		assert!(TestRuntime::listed_for_sale(0, 10).is_empty());
+		// Once the auction ends without bids, the old listing does not come back.
+		run_to_block(11);
+		assert!(!Auctions::<TestRuntime>::contains_key([1u8; 32]));
+		assert!(TestRuntime::listed_for_sale(0, 10).is_empty());
+		assert_noop!(
+			PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [1u8; 32], 100),
+			Error::<TestRuntime>::NotForSale
+		);
+	})
+}
+
+#[test]
+fn start_auction_emits_event() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 100, 10));
+		System::assert_last_event(
+			Event::<TestRuntime>::AuctionStarted {
+				seller: ALICE,
+				kitty_id: [1u8; 32],
+				reserve: 100,
+				end: 11,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn start_auction_logic_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(ALICE), [1u8; 32], Some(1337)));
+		// Cannot auction a kitty which does not exist.
+		assert_noop!(
+			PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [0u8; 32], 100, 10),
+			Error::<TestRuntime>::NoKitty
+		);
+		// Cannot auction a kitty you do not own.
+		assert_noop!(
+			PalletKitties::start_auction(RuntimeOrigin::signed(BOB), [1u8; 32], 100, 10),
+			Error::<TestRuntime>::NotOwner
+		);
+		// The duration must be within bounds.
+		assert_noop!(
+			PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 100, 0),
+			Error::<TestRuntime>::InvalidAuctionDuration
+		);
+		assert_noop!(
+			PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 100, 101),
+			Error::<TestRuntime>::InvalidAuctionDuration
+		);
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 100, 10));
+		let auction = Auctions::<TestRuntime>::get([1u8; 32]).unwrap();
+		assert_eq!(auction.seller, ALICE);
+		assert_eq!(auction.end, 11);
+		assert_eq!(AuctionsEndingAt::<TestRuntime>::get(11), vec![[1u8; 32]]);
+		// The fixed price listing is removed.
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().price, None);
+		// The kitty cannot be auctioned twice, re-listed, or transferred.
+		assert_noop!(
+			PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 100, 10),
+			Error::<TestRuntime>::KittyInAuction
+		);
+		assert_noop!(
+			PalletKitties::set_price(RuntimeOrigin::signed(ALICE), [1u8; 32], Some(1337)),
+			Error::<TestRuntime>::KittyInAuction
+		);
+		assert_noop!(
+			PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]),
+			Error::<TestRuntime>::KittyInAuction
+		);
+		// Only a limited number of auctions can end in the same block.
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [3u8; 32], None));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [2u8; 32], 100, 10));
+		assert_noop!(
+			PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [3u8; 32], 100, 10),
+			Error::<TestRuntime>::TooManyAuctions
+		);
+	})
+}
+
+#[test]
+fn bid_logic_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let reason = HoldReason::AuctionBid.into();
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletBalances::mint_into(&3, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		// Cannot bid on a kitty which is not being auctioned.
+		assert_noop!(
+			PalletKitties::bid(RuntimeOrigin::signed(BOB), [1u8; 32], 100),
+			Error::<TestRuntime>::NoAuction
+		);
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 100, 10));
+		// Cannot bid on your own kitty.
+		assert_noop!(
+			PalletKitties::bid(RuntimeOrigin::signed(ALICE), [1u8; 32], 100),
+			Error::<TestRuntime>::BidOnOwnKitty
+		);
+		// Cannot bid below the reserve.
+		assert_noop!(
+			PalletKitties::bid(RuntimeOrigin::signed(BOB), [1u8; 32], 99),
+			Error::<TestRuntime>::BidTooLow
+		);
+		// Cannot bid more than you have.
+		assert_noop!(
+			PalletKitties::bid(RuntimeOrigin::signed(BOB), [1u8; 32], 1_001),
+			frame::deps::sp_runtime::TokenError::FundsUnavailable
+		);
+		// A valid bid holds the funds of the bidder.
+		assert_ok!(PalletKitties::bid(RuntimeOrigin::signed(BOB), [1u8; 32], 100));
+		System::assert_last_event(
+			Event::<TestRuntime>::BidPlaced { bidder: BOB, kitty_id: [1u8; 32], amount: 100 }
+				.into(),
+		);
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &BOB), 100);
+		assert_eq!(PalletBalances::balance(&BOB), 900);
+		// The next bid must be higher.
+		assert_noop!(
+			PalletKitties::bid(RuntimeOrigin::signed(3), [1u8; 32], 100),
+			Error::<TestRuntime>::BidTooLow
+		);
+		// Being outbid refunds the previous bidder.
+		assert_ok!(PalletKitties::bid(RuntimeOrigin::signed(3), [1u8; 32], 150));
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &BOB), 0);
+		assert_eq!(PalletBalances::balance(&BOB), 1_000);
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &3), 150);
+		// Bidders can raise their own bid.
+		assert_ok!(PalletKitties::bid(RuntimeOrigin::signed(3), [1u8; 32], 200));
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &3), 200);
+		assert_eq!(PalletBalances::balance(&3), 800);
+		// No bids are accepted once the auction has ended.
+		System::set_block_number(11);
+		assert_noop!(
+			PalletKitties::bid(RuntimeOrigin::signed(BOB), [1u8; 32], 300),
+			Error::<TestRuntime>::AuctionEnded
+		);
+	})
+}
+
+#[test]
+fn auction_settles_to_highest_bidder() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let reason = HoldReason::AuctionBid.into();
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1_000));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 100, 10));
+		assert_ok!(PalletKitties::bid(RuntimeOrigin::signed(BOB), [1u8; 32], 250));
+		// Nothing happens before the end block.
+		run_to_block(10);
+		assert!(Auctions::<TestRuntime>::contains_key([1u8; 32]));
+		// At the end block, the kitty and the held funds change hands.
+		run_to_block(11);
+		System::assert_last_event(
+			Event::<TestRuntime>::AuctionSettled {
+				seller: ALICE,
+				kitty_id: [1u8; 32],
+				winning_bid: Some((BOB, 250)),
+			}
+			.into(),
+		);
+		assert!(!Auctions::<TestRuntime>::contains_key([1u8; 32]));
+		assert!(AuctionsEndingAt::<TestRuntime>::get(11).is_empty());
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, BOB);
+		assert_eq!(PalletKitties::kitties_of(BOB), vec![[1u8; 32]]);
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &BOB), 0);
+		assert_eq!(PalletBalances::balance(&BOB), 750);
+		assert_eq!(PalletBalances::balance(&ALICE), 1_250);
+	})
+}
+
+#[test]
+fn auction_without_bids_returns_kitty() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 100, 5));
+		run_to_block(6);
+		System::assert_last_event(
+			Event::<TestRuntime>::AuctionSettled {
+				seller: ALICE,
+				kitty_id: [1u8; 32],
+				winning_bid: None,
+			}
+			.into(),
+		);
+		// The seller keeps the kitty, and can use it again.
+		assert!(!Auctions::<TestRuntime>::contains_key([1u8; 32]));
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, ALICE);
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]));
+	})
+}
+
+#[test]
+fn failed_settlement_refunds_bidder() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let reason = HoldReason::AuctionBid.into();
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1_000));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 100, 10));
+		assert_ok!(PalletKitties::bid(RuntimeOrigin::signed(BOB), [1u8; 32], 250));
+		// BOB can no longer receive any more kitties.
+		for i in 0..100u8 {
+			assert_ok!(PalletKitties::mint(BOB, [i + 2; 32], None));
+		}
+		run_to_block(11);
+		System::assert_last_event(
+			Event::<TestRuntime>::AuctionSettled {
+				seller: ALICE,
+				kitty_id: [1u8; 32],
+				winning_bid: None,
+			}
+			.into(),
+		);
+		// Nothing changed hands, and BOB got their funds back.
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, ALICE);
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &BOB), 0);
+		assert_eq!(PalletBalances::balance(&BOB), 1_000);
+		assert_eq!(PalletBalances::balance(&ALICE), 1_000);
+	})
+}
+
+#[test]
+fn dutch_auction_price_at_boundaries() {
+	let auction =
+		DutchAuction::<TestRuntime> { start_price: 1_000, end_price: 100, start: 10, end: 20 };
+	// Before and at the start, the price is the start price.
+	assert_eq!(PalletKitties::dutch_auction_price(&auction, 0), 1_000);
+	assert_eq!(PalletKitties::dutch_auction_price(&auction, 10), 1_000);
+	// The price decays linearly.
+	assert_eq!(PalletKitties::dutch_auction_price(&auction, 11), 910);
+	assert_eq!(PalletKitties::dutch_auction_price(&auction, 15), 550);
+	assert_eq!(PalletKitties::dutch_auction_price(&auction, 19), 190);
+	// At and after the end, the price is the end price.
+	assert_eq!(PalletKitties::dutch_auction_price(&auction, 20), 100);
+	assert_eq!(PalletKitties::dutch_auction_price(&auction, 1_000), 100);
+	// Prices which do not divide evenly are rounded down.
+	let auction = DutchAuction::<TestRuntime> { start_price: 10, end_price: 0, start: 0, end: 3 };
+	assert_eq!(PalletKitties::dutch_auction_price(&auction, 1), 6);
+	assert_eq!(PalletKitties::dutch_auction_price(&auction, 2), 3);
+	// A constant price auction never changes.
+	let auction = DutchAuction::<TestRuntime> { start_price: 5, end_price: 5, start: 0, end: 3 };
+	assert_eq!(PalletKitties::dutch_auction_price(&auction, 2), 5);
+}
+
+#[test]
+fn start_dutch_auction_logic_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(ALICE), [1u8; 32], Some(1337)));
+		assert_noop!(
+			PalletKitties::start_dutch_auction(RuntimeOrigin::signed(BOB), [1u8; 32], 100, 10, 10),
+			Error::<TestRuntime>::NotOwner
+		);
+		assert_noop!(
+			PalletKitties::start_dutch_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 100, 10, 0),
+			Error::<TestRuntime>::InvalidAuctionDuration
+		);
+		// The price can only go down.
+		assert_noop!(
+			PalletKitties::start_dutch_auction(
+				RuntimeOrigin::signed(ALICE),
+				[1u8; 32],
+				10,
+				100,
+				10
+			),
+			Error::<TestRuntime>::InvalidDutchAuction
+		);
+		assert_ok!(PalletKitties::start_dutch_auction(
+			RuntimeOrigin::signed(ALICE),
+			[1u8; 32],
+			1_000,
+			100,
+			10
+		));
+		System::assert_last_event(
+			Event::<TestRuntime>::DutchAuctionStarted {
+				seller: ALICE,
+				kitty_id: [1u8; 32],
+				start_price: 1_000,
+				end_price: 100,
+				end: 11,
+			}
+			.into(),
+		);
+		// The fixed price is replaced by the descending price.
+		let kitty = Kitties::<TestRuntime>::get([1u8; 32]).unwrap();
+		assert_eq!(kitty.price, None);
+		assert_eq!(PalletKitties::current_price([1u8; 32], &kitty), Some(1_000));
+		// Setting a fixed price replaces the descending price again.
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(ALICE), [1u8; 32], Some(1337)));
+		assert!(!DutchAuctions::<TestRuntime>::contains_key([1u8; 32]));
+	})
+}
+
+#[test]
+fn buy_kitty_in_dutch_auction_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1));
+		assert_ok!(PalletBalances::mint_into(&BOB, 10_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::start_dutch_auction(
+			RuntimeOrigin::signed(ALICE),
+			[1u8; 32],
+			1_000,
+			100,
+			10
+		));
+		// Half way through the auction, the price has dropped by half.
+		System::set_block_number(6);
+		assert_noop!(
+			PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [1u8; 32], 549),
+			Error::<TestRuntime>::MaxPriceTooLow
+		);
+		// The buyer only pays the current price, even if willing to pay more.
+		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [1u8; 32], 1_000));
+		System::assert_last_event(
+			Event::<TestRuntime>::Sold {
+				buyer: BOB,
+				kitty_id: [1u8; 32],
+				price: 550,
+				royalty: 0,
+				fee: 0,
+			}
+			.into(),
+		);
+		assert_eq!(PalletBalances::balance(&ALICE), 551);
+		assert_eq!(PalletBalances::balance(&BOB), 9_450);
+		// The listing is removed once sold.
+		assert!(!DutchAuctions::<TestRuntime>::contains_key([1u8; 32]));
+		assert_noop!(
+			PalletKitties::buy_kitty(RuntimeOrigin::signed(ALICE), [1u8; 32], 1_000),
+			Error::<TestRuntime>::NotForSale
+		);
+	})
+}
+
+#[test]
+fn make_offer_logic_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let reason = HoldReason::Offer.into();
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		// Cannot make an offer for a kitty which does not exist.
+		assert_noop!(
+			PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [0u8; 32], 100, 10),
+			Error::<TestRuntime>::NoKitty
+		);
+		// Cannot make an offer for your own kitty.
+		assert_noop!(
+			PalletKitties::make_offer(RuntimeOrigin::signed(ALICE), [1u8; 32], 100, 10),
+			Error::<TestRuntime>::OfferOnOwnKitty
+		);
+		// Cannot make an offer which has already expired.
+		assert_noop!(
+			PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 100, 1),
+			Error::<TestRuntime>::OfferExpired
+		);
+		// Cannot make an offer which stays open for longer than `MaxOfferDuration`.
+		assert_noop!(
+			PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 100, 102),
+			Error::<TestRuntime>::InvalidOfferDuration
+		);
+		// Cannot offer less than `MinOfferAmount`.
+		assert_noop!(
+			PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 9, 10),
+			Error::<TestRuntime>::OfferTooLow
+		);
+		// Cannot offer more than you have.
+		assert_noop!(
+			PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 1_001, 10),
+			frame::deps::sp_runtime::TokenError::FundsUnavailable
+		);
+		// Offers can be made for kitties which are not for sale, and hold the offered funds.
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 100, 10));
+		System::assert_last_event(
+			Event::<TestRuntime>::OfferMade {
+				bidder: BOB,
+				kitty_id: [1u8; 32],
+				amount: 100,
+				expires_at: 10,
+			}
+			.into(),
+		);
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &BOB), 100);
+		// A new offer replaces the previous one, and no longer expires at its original block.
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 300, 20));
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &BOB), 300);
+		assert_eq!(Offers::<TestRuntime>::get([1u8; 32]).len(), 1);
+		assert!(!OffersExpiringAt::<TestRuntime>::contains_key(10));
+		// The number of offers per kitty is bounded, and a full kitty only takes offers which beat
+		// the lowest one.
+		assert_ok!(PalletBalances::mint_into(&3, 1_000));
+		assert_ok!(PalletBalances::mint_into(&4, 1_000));
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(3), [1u8; 32], 100, 30));
+		assert_noop!(
+			PalletKitties::make_offer(RuntimeOrigin::signed(4), [1u8; 32], 100, 40),
+			Error::<TestRuntime>::TooManyOffers
+		);
+		// The lowest offer is refunded when it is beaten.
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(4), [1u8; 32], 101, 40));
+		System::assert_has_event(
+			Event::<TestRuntime>::OfferOutbid { bidder: 3, kitty_id: [1u8; 32] }.into(),
+		);
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &3), 0);
+		assert_eq!(PalletBalances::balance(&3), 1_000);
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &4), 101);
+		assert!(!OffersExpiringAt::<TestRuntime>::contains_key(30));
+		let bidders: Vec<_> =
+			Offers::<TestRuntime>::get([1u8; 32]).iter().map(|offer| offer.bidder).collect();
+		assert_eq!(bidders, vec![BOB, 4]);
+	})
+}
+
+#[test]
+fn withdraw_offer_logic_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_noop!(
+			PalletKitties::withdraw_offer(RuntimeOrigin::signed(BOB), [1u8; 32]),
+			Error::<TestRuntime>::NoOffer
+		);
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 100, 10));
+		assert_ok!(PalletKitties::withdraw_offer(RuntimeOrigin::signed(BOB), [1u8; 32]));
+		System::assert_last_event(
+			Event::<TestRuntime>::OfferWithdrawn { bidder: BOB, kitty_id: [1u8; 32] }.into(),
+		);
+		assert_eq!(PalletBalances::balance_on_hold(&HoldReason::Offer.into(), &BOB), 0);
+		assert_eq!(PalletBalances::balance(&BOB), 1_000);
+		assert!(!Offers::<TestRuntime>::contains_key([1u8; 32]));
+		assert!(!OffersExpiringAt::<TestRuntime>::contains_key(10));
+	})
+}
+
+#[test]
+fn accept_offer_logic_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1_000));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 250, 10));
+		// Only the owner can accept an offer.
+		assert_noop!(
+			PalletKitties::accept_offer(RuntimeOrigin::signed(BOB), [1u8; 32], BOB),
+			Error::<TestRuntime>::NotOwner
+		);
+		assert_noop!(
+			PalletKitties::accept_offer(RuntimeOrigin::signed(ALICE), [1u8; 32], 3),
+			Error::<TestRuntime>::NoOffer
+		);
+		assert_ok!(PalletKitties::accept_offer(RuntimeOrigin::signed(ALICE), [1u8; 32], BOB));
+		System::assert_last_event(
+			Event::<TestRuntime>::OfferAccepted {
+				owner: ALICE,
+				bidder: BOB,
+				kitty_id: [1u8; 32],
+				amount: 250,
+			}
+			.into(),
+		);
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, BOB);
+		assert_eq!(PalletBalances::balance_on_hold(&HoldReason::Offer.into(), &BOB), 0);
+		assert_eq!(PalletBalances::balance(&BOB), 750);
+		assert_eq!(PalletBalances::balance(&ALICE), 1_250);
+		assert!(!Offers::<TestRuntime>::contains_key([1u8; 32]));
+	})
+}
+
+#[test]
+fn offers_expire_automatically() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 100, 10));
+		// An offer which was replaced does not expire at its original block.
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 200, 5));
+		run_to_block(5);
+		System::assert_last_event(
+			Event::<TestRuntime>::OfferExpired { bidder: BOB, kitty_id: [1u8; 32] }.into(),
+		);
+		assert!(!Offers::<TestRuntime>::contains_key([1u8; 32]));
+		assert_eq!(PalletBalances::balance_on_hold(&HoldReason::Offer.into(), &BOB), 0);
+		assert_eq!(PalletBalances::balance(&BOB), 1_000);
+		// The offer which was replaced does not expire a newer offer at block 10.
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 300, 20));
+		run_to_block(10);
+		assert_eq!(Offers::<TestRuntime>::get([1u8; 32]).len(), 1);
+		assert_eq!(PalletBalances::balance_on_hold(&HoldReason::Offer.into(), &BOB), 300);
+		// Expired offers can no longer be accepted, even before they are cleaned up.
+		System::set_block_number(20);
+		assert_noop!(
+			PalletKitties::accept_offer(RuntimeOrigin::signed(ALICE), [1u8; 32], BOB),
+			Error::<TestRuntime>::OfferExpired
+		);
+	})
+}
+
+#[test]
+fn mint_records_creator() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]));
+		// The creator does not change when the kitty changes owner.
+		assert_eq!(Creators::<TestRuntime>::get([1u8; 32]), Some(ALICE));
+	})
+}
+
+#[test]
+fn buy_kitty_pays_royalty_to_creator() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1));
+		assert_ok!(PalletBalances::mint_into(&3, 100_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]));
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(BOB), [1u8; 32], Some(1_000)));
+		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(3), [1u8; 32], 1_000));
+		System::assert_last_event(
+			Event::<TestRuntime>::Sold {
+				buyer: 3,
+				kitty_id: [1u8; 32],
+				price: 1_000,
+				royalty: 100,
+				fee: 0,
+			}
+			.into(),
+		);
+		// The creator receives 10%, and the seller receives the rest.
+		assert_eq!(PalletBalances::balance(&ALICE), 101);
+		assert_eq!(PalletBalances::balance(&BOB), 901);
+		assert_eq!(PalletBalances::balance(&3), 99_000);
+	})
+}
+
+#[test]
+fn royalty_goes_to_seller_when_creator_cannot_receive_it() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		ExistentialDeposit::set(&10);
+		assert_ok!(PalletBalances::mint_into(&BOB, 100));
+		assert_ok!(PalletBalances::mint_into(&CHARLIE, 10_000));
+		// The creator has no account, and a royalty of 5 is below the existential deposit.
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]));
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(BOB), [1u8; 32], Some(50)));
+		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(CHARLIE), [1u8; 32], 50));
+		System::assert_last_event(
+			Event::<TestRuntime>::Sold {
+				buyer: CHARLIE,
+				kitty_id: [1u8; 32],
+				price: 50,
+				royalty: 0,
+				fee: 0,
+			}
+			.into(),
+		);
+		assert_eq!(PalletBalances::balance(&ALICE), 0);
+		assert_eq!(PalletBalances::balance(&BOB), 150);
+		// The same applies when the kitty is sold through an offer or an auction.
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 50, 10));
+		assert_ok!(PalletKitties::accept_offer(RuntimeOrigin::signed(CHARLIE), [1u8; 32], BOB));
+		assert_eq!(PalletBalances::balance(&ALICE), 0);
+		assert_eq!(PalletBalances::balance(&CHARLIE), 10_000);
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(BOB), [1u8; 32], 10, 10));
+		assert_ok!(PalletKitties::bid(RuntimeOrigin::signed(CHARLIE), [1u8; 32], 50));
+		run_to_block(11);
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, CHARLIE);
+		assert_eq!(PalletBalances::balance(&ALICE), 0);
+		assert_eq!(PalletBalances::balance(&BOB), 150);
+	})
+}
+
+#[test]
+fn royalty_rounds_down() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1));
+		assert_ok!(PalletBalances::mint_into(&3, 100_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]));
+		// 10% of 1337 is 133.7, which is rounded down in favor of the seller.
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(BOB), [1u8; 32], Some(1337)));
+		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(3), [1u8; 32], 1337));
+		assert_eq!(PalletBalances::balance(&ALICE), 1 + 133);
+		assert_eq!(PalletBalances::balance(&BOB), 1 + 1204);
+		// Very small prices result in no royalty at all.
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(3), [1u8; 32], Some(9)));
+		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [1u8; 32], 9));
+		System::assert_last_event(
+			Event::<TestRuntime>::Sold {
+				buyer: BOB,
+				kitty_id: [1u8; 32],
+				price: 9,
+				royalty: 0,
+				fee: 0,
+			}
+			.into(),
+		);
+		assert_eq!(PalletBalances::balance(&ALICE), 1 + 133);
+	})
+}
+
+#[test]
+fn no_royalty_when_creator_is_seller() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&BOB, 100_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(ALICE), [1u8; 32], Some(1_000)));
+		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [1u8; 32], 1_000));
+		System::assert_last_event(
+			Event::<TestRuntime>::Sold {
+				buyer: BOB,
+				kitty_id: [1u8; 32],
+				price: 1_000,
+				royalty: 0,
+				fee: 0,
+			}
+			.into(),
+		);
+		assert_eq!(PalletBalances::balance(&ALICE), 1_000);
+	})
+}
+
+#[test]
+fn auction_sale_pays_royalty_to_creator() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1));
+		assert_ok!(PalletBalances::mint_into(&CHARLIE, 100_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(BOB), [1u8; 32], 100, 10));
+		assert_ok!(PalletKitties::bid(RuntimeOrigin::signed(CHARLIE), [1u8; 32], 1_000));
+		run_to_block(11);
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, CHARLIE);
+		// The creator receives 10% of the winning bid, and the seller receives the rest.
+		assert_eq!(PalletBalances::balance(&ALICE), 1 + 100);
+		assert_eq!(PalletBalances::balance(&BOB), 1 + 900);
+		assert_eq!(PalletBalances::total_balance(&CHARLIE), 99_000);
+	})
+}
+
+#[test]
+fn accepted_offer_pays_royalty_to_creator() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1));
+		assert_ok!(PalletBalances::mint_into(&CHARLIE, 100_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]));
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(CHARLIE), [1u8; 32], 1_000, 10));
+		assert_ok!(PalletKitties::accept_offer(RuntimeOrigin::signed(BOB), [1u8; 32], CHARLIE));
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, CHARLIE);
+		// The creator receives 10% of the offer, and the seller receives the rest.
+		assert_eq!(PalletBalances::balance(&ALICE), 1 + 100);
+		assert_eq!(PalletBalances::balance(&BOB), 1 + 900);
+		assert_eq!(PalletBalances::total_balance(&CHARLIE), 99_000);
+	})
+}
+
+#[test]
+fn buy_kitty_pays_marketplace_fee() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MarketplaceFee::set(&Permill::from_percent(5));
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1));
+		assert_ok!(PalletBalances::mint_into(&TREASURY, 1));
+		assert_ok!(PalletBalances::mint_into(&3, 100_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]));
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(BOB), [1u8; 32], Some(1_000)));
+		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(3), [1u8; 32], 1_000));
+		System::assert_last_event(
+			Event::<TestRuntime>::Sold {
+				buyer: 3,
+				kitty_id: [1u8; 32],
+				price: 1_000,
+				royalty: 100,
+				fee: 50,
+			}
+			.into(),
+		);
+		// The fee goes to the treasury, the royalty to the creator, and the rest to the seller.
+		assert_eq!(PalletBalances::balance(&TREASURY), 1 + 50);
+		assert_eq!(PalletBalances::balance(&ALICE), 1 + 100);
+		assert_eq!(PalletBalances::balance(&BOB), 1 + 850);
+		assert_eq!(PalletBalances::balance(&3), 99_000);
+	})
+}
+
+#[test]
+fn auction_sale_pays_marketplace_fee() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MarketplaceFee::set(&Permill::from_percent(5));
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1));
+		assert_ok!(PalletBalances::mint_into(&TREASURY, 1));
+		assert_ok!(PalletBalances::mint_into(&CHARLIE, 100_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(BOB), [1u8; 32], 100, 10));
+		assert_ok!(PalletKitties::bid(RuntimeOrigin::signed(CHARLIE), [1u8; 32], 1_000));
+		run_to_block(11);
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, CHARLIE);
+		// The fee goes to the treasury, the royalty to the creator, and the rest to the seller.
+		assert_eq!(PalletBalances::balance(&TREASURY), 1 + 50);
+		assert_eq!(PalletBalances::balance(&ALICE), 1 + 100);
+		assert_eq!(PalletBalances::balance(&BOB), 1 + 850);
+		assert_eq!(PalletBalances::total_balance(&CHARLIE), 99_000);
+	})
+}
+
+#[test]
+fn accepted_offer_pays_marketplace_fee() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MarketplaceFee::set(&Permill::from_percent(5));
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1));
+		assert_ok!(PalletBalances::mint_into(&TREASURY, 1));
+		assert_ok!(PalletBalances::mint_into(&CHARLIE, 100_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]));
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(CHARLIE), [1u8; 32], 1_000, 10));
+		assert_ok!(PalletKitties::accept_offer(RuntimeOrigin::signed(BOB), [1u8; 32], CHARLIE));
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, CHARLIE);
+		// The fee goes to the treasury, the royalty to the creator, and the rest to the seller.
+		assert_eq!(PalletBalances::balance(&TREASURY), 1 + 50);
+		assert_eq!(PalletBalances::balance(&ALICE), 1 + 100);
+		assert_eq!(PalletBalances::balance(&BOB), 1 + 850);
+		assert_eq!(PalletBalances::total_balance(&CHARLIE), 99_000);
+	})
+}
+
+#[test]
+fn marketplace_fee_is_atomic_with_sale() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MarketplaceFee::set(&Permill::from_percent(5));
+		assert_ok!(PalletBalances::mint_into(&TREASURY, 1));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(ALICE), [1u8; 32], Some(1_000)));
+		// BOB can pay the fee, but not the rest of the price without killing their account.
+		assert_noop!(
+			PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [1u8; 32], 1_000),
+			frame::deps::sp_runtime::TokenError::NotExpendable
+		);
+		// Nothing was paid, and the kitty did not move.
+		assert_eq!(PalletBalances::balance(&TREASURY), 1);
+		assert_eq!(PalletBalances::balance(&BOB), 1_000);
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, ALICE);
+	})
+}
+
+#[test]
+fn burn_logic_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert_noop!(
+			PalletKitties::burn(RuntimeOrigin::signed(ALICE), [3u8; 32]),
+			Error::<TestRuntime>::NoKitty
+		);
+		assert_noop!(
+			PalletKitties::burn(RuntimeOrigin::signed(BOB), [1u8; 32]),
+			Error::<TestRuntime>::NotOwner
+		);
+		assert_ok!(PalletKitties::burn(RuntimeOrigin::signed(ALICE), [1u8; 32]));
+		System::assert_last_event(
+			Event::<TestRuntime>::Burned { owner: ALICE, kitty_id: [1u8; 32] }.into(),
+		);
+		assert!(!Kitties::<TestRuntime>::contains_key([1u8; 32]));
+		assert!(!Creators::<TestRuntime>::contains_key([1u8; 32]));
+		assert_eq!(PalletKitties::kitties_of(ALICE), vec![[2u8; 32]]);
+		assert_eq!(CountForKittiesOwned::<TestRuntime>::get(ALICE), 1);
+		assert_eq!(CountForKitties::<TestRuntime>::get(), 1);
+		// Burning the last kitty of an account removes its count.
+		assert_ok!(PalletKitties::burn(RuntimeOrigin::signed(ALICE), [2u8; 32]));
+		assert!(!CountForKittiesOwned::<TestRuntime>::contains_key(ALICE));
+	})
+}
+
+#[test]
+fn burned_kitties_cannot_be_minted_again() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::burn(RuntimeOrigin::signed(ALICE), [1u8; 32]));
+		assert_noop!(PalletKitties::mint(BOB, [1u8; 32], None), Error::<TestRuntime>::KittyBurned);
+	})
+}
+
+#[test]
+fn burn_clears_listings_and_refunds_offers() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::start_dutch_auction(
+			RuntimeOrigin::signed(ALICE),
+			[1u8; 32],
+			100,
+			10,
+			10
+		));
+		assert_ok!(PalletKitties::make_offer(RuntimeOrigin::signed(BOB), [1u8; 32], 100, 10));
+		assert_ok!(PalletKitties::burn(RuntimeOrigin::signed(ALICE), [1u8; 32]));
+		assert!(!DutchAuctions::<TestRuntime>::contains_key([1u8; 32]));
+		assert!(!Offers::<TestRuntime>::contains_key([1u8; 32]));
+		assert_eq!(PalletBalances::balance_on_hold(&HoldReason::Offer.into(), &BOB), 0);
+		assert_eq!(PalletBalances::balance(&BOB), 1_000);
+		// The offer no longer exists when it would have expired.
+		run_to_block(10);
+		assert_eq!(PalletBalances::balance(&BOB), 1_000);
+	})
+}
+
+#[test]
+fn cannot_burn_kitty_in_auction() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 100, 10));
+		assert_noop!(
+			PalletKitties::burn(RuntimeOrigin::signed(ALICE), [1u8; 32]),
+			Error::<TestRuntime>::KittyInAuction
+		);
+	})
+}
+
+#[test]
+fn approve_transfer_logic_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_noop!(
+			PalletKitties::approve_transfer(RuntimeOrigin::signed(ALICE), [2u8; 32], BOB, None),
+			Error::<TestRuntime>::NoKitty
+		);
+		assert_noop!(
+			PalletKitties::approve_transfer(RuntimeOrigin::signed(BOB), [1u8; 32], CHARLIE, None),
+			Error::<TestRuntime>::NotOwner
+		);
+		assert_noop!(
+			PalletKitties::approve_transfer(RuntimeOrigin::signed(ALICE), [1u8; 32], ALICE, None),
+			Error::<TestRuntime>::ApproveSelf
+		);
+		assert_noop!(
+			PalletKitties::approve_transfer(RuntimeOrigin::signed(ALICE), [1u8; 32], BOB, Some(1)),
+			Error::<TestRuntime>::ApprovalExpired
+		);
+		assert_ok!(PalletKitties::approve_transfer(
+			RuntimeOrigin::signed(ALICE),
+			[1u8; 32],
+			BOB,
+			Some(10)
+		));
+		System::assert_last_event(
+			Event::<TestRuntime>::TransferApproved {
+				owner: ALICE,
+				kitty_id: [1u8; 32],
+				delegate: BOB,
+				expires_at: Some(10),
+			}
+			.into(),
+		);
+		let approval = Approvals::<TestRuntime>::get([1u8; 32]).unwrap();
+		assert_eq!(approval.delegate, BOB);
+		assert_eq!(approval.expires_at, Some(10));
+	})
+}
+
+#[test]
+fn cancel_approval_logic_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_noop!(
+			PalletKitties::cancel_approval(RuntimeOrigin::signed(ALICE), [1u8; 32]),
+			Error::<TestRuntime>::NoApproval
+		);
+		assert_ok!(PalletKitties::approve_transfer(
+			RuntimeOrigin::signed(ALICE),
+			[1u8; 32],
+			BOB,
+			None
+		));
+		assert_noop!(
+			PalletKitties::cancel_approval(RuntimeOrigin::signed(BOB), [1u8; 32]),
+			Error::<TestRuntime>::NotOwner
+		);
+		assert_ok!(PalletKitties::cancel_approval(RuntimeOrigin::signed(ALICE), [1u8; 32]));
+		System::assert_last_event(
+			Event::<TestRuntime>::ApprovalCancelled { owner: ALICE, kitty_id: [1u8; 32] }.into(),
+		);
+		assert!(!Approvals::<TestRuntime>::contains_key([1u8; 32]));
+		assert_noop!(
+			PalletKitties::transfer_from(RuntimeOrigin::signed(BOB), [1u8; 32], BOB),
+			Error::<TestRuntime>::NoApproval
+		);
+	})
+}
+
+#[test]
+fn transfer_from_logic_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::approve_transfer(
+			RuntimeOrigin::signed(ALICE),
+			[1u8; 32],
+			BOB,
+			Some(10)
+		));
+		assert_noop!(
+			PalletKitties::transfer_from(RuntimeOrigin::signed(CHARLIE), [1u8; 32], CHARLIE),
+			Error::<TestRuntime>::NotDelegate
+		);
+		// The delegate moves the kitty on behalf of the owner.
+		assert_ok!(PalletKitties::transfer_from(RuntimeOrigin::signed(BOB), [1u8; 32], CHARLIE));
+		System::assert_last_event(
+			Event::<TestRuntime>::Transferred { from: ALICE, to: CHARLIE, kitty_id: [1u8; 32] }
+				.into(),
+		);
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, CHARLIE);
+		// The approval does not carry over to the new owner.
+		assert!(!Approvals::<TestRuntime>::contains_key([1u8; 32]));
+		assert_noop!(
+			PalletKitties::transfer_from(RuntimeOrigin::signed(BOB), [1u8; 32], BOB),
+			Error::<TestRuntime>::NoApproval
+		);
+	})
+}
+
+#[test]
+fn approvals_expire() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::approve_transfer(
+			RuntimeOrigin::signed(ALICE),
+			[1u8; 32],
+			BOB,
+			Some(10)
+		));
+		System::set_block_number(10);
+		assert_noop!(
+			PalletKitties::transfer_from(RuntimeOrigin::signed(BOB), [1u8; 32], BOB),
+			Error::<TestRuntime>::ApprovalExpired
+		);
+	})
+}
+
+#[test]
+fn approvals_are_cleared_on_sale() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1_000));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::approve_transfer(
+			RuntimeOrigin::signed(ALICE),
+			[1u8; 32],
+			CHARLIE,
+			None
+		));
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(ALICE), [1u8; 32], Some(100)));
+		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [1u8; 32], 100));
+		assert!(!Approvals::<TestRuntime>::contains_key([1u8; 32]));
+	})
+}
+
+#[test]
+fn set_approval_for_all_logic_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			PalletKitties::set_approval_for_all(RuntimeOrigin::signed(ALICE), ALICE, true),
+			Error::<TestRuntime>::ApproveSelf
+		);
+		assert_ok!(PalletKitties::set_approval_for_all(RuntimeOrigin::signed(ALICE), BOB, true));
+		System::assert_last_event(
+			Event::<TestRuntime>::ApprovalForAllSet { owner: ALICE, operator: BOB, approved: true }
+				.into(),
+		);
+		assert!(OperatorApprovals::<TestRuntime>::contains_key(ALICE, BOB));
+		// Approvals are one way.
+		assert!(!PalletKitties::is_owner_or_operator(&BOB, &ALICE));
+		assert_ok!(PalletKitties::set_approval_for_all(RuntimeOrigin::signed(ALICE), BOB, false));
+		System::assert_last_event(
+			Event::<TestRuntime>::ApprovalForAllSet {
+				owner: ALICE,
+				operator: BOB,
+				approved: false,
+			}
+			.into(),
+		);
+		assert!(!OperatorApprovals::<TestRuntime>::contains_key(ALICE, BOB));
+	})
+}
+
+#[test]
+fn operators_can_manage_all_kitties_of_owner() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert_noop!(
+			PalletKitties::set_price(RuntimeOrigin::signed(BOB), [1u8; 32], Some(100)),
+			Error::<TestRuntime>::NotOwner
+		);
+		assert_ok!(PalletKitties::set_approval_for_all(RuntimeOrigin::signed(ALICE), BOB, true));
+		// The operator can list and delist, and events name the real owner.
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(BOB), [1u8; 32], Some(100)));
+		System::assert_last_event(
+			Event::<TestRuntime>::PriceSet {
+				owner: ALICE,
+				kitty_id: [1u8; 32],
+				new_price: Some(100),
+			}
+			.into(),
+		);
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(BOB), [1u8; 32], None));
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().price, None);
+		// The operator can also send a kitty to themselves.
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(BOB), BOB, [1u8; 32]));
+		System::assert_last_event(
+			Event::<TestRuntime>::Transferred { from: ALICE, to: BOB, kitty_id: [1u8; 32] }.into(),
+		);
+		assert_eq!(PalletKitties::kitties_of(BOB), vec![[1u8; 32]]);
+		assert_eq!(CountForKittiesOwned::<TestRuntime>::get(ALICE), 1);
+		// The operator cannot send a kitty back to its owner.
+		assert_noop!(
+			PalletKitties::transfer(RuntimeOrigin::signed(BOB), ALICE, [2u8; 32]),
+			Error::<TestRuntime>::TransferToSelf
+		);
+		// Once revoked, the operator can no longer act for the owner.
+		assert_ok!(PalletKitties::set_approval_for_all(RuntimeOrigin::signed(ALICE), BOB, false));
+		assert_noop!(
+			PalletKitties::transfer(RuntimeOrigin::signed(BOB), CHARLIE, [2u8; 32]),
+			Error::<TestRuntime>::NotOwner
+		);
+	})
+}
+
+#[test]
+fn operators_can_start_auctions_for_owner() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert_noop!(
+			PalletKitties::start_auction(RuntimeOrigin::signed(BOB), [1u8; 32], 10, 10),
+			Error::<TestRuntime>::NotOwner
+		);
+		assert_noop!(
+			PalletKitties::start_dutch_auction(RuntimeOrigin::signed(BOB), [2u8; 32], 20, 10, 10),
+			Error::<TestRuntime>::NotOwner
+		);
+		assert_ok!(PalletKitties::set_approval_for_all(RuntimeOrigin::signed(ALICE), BOB, true));
+		// The owner is the seller of an auction started by their operator.
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(BOB), [1u8; 32], 10, 10));
+		System::assert_last_event(
+			Event::<TestRuntime>::AuctionStarted {
+				seller: ALICE,
+				kitty_id: [1u8; 32],
+				reserve: 10,
+				end: 11,
+			}
+			.into(),
+		);
+		assert_eq!(Auctions::<TestRuntime>::get([1u8; 32]).unwrap().seller, ALICE);
+		assert_ok!(PalletKitties::start_dutch_auction(
+			RuntimeOrigin::signed(BOB),
+			[2u8; 32],
+			20,
+			10,
+			10
+		));
+		System::assert_last_event(
+			Event::<TestRuntime>::DutchAuctionStarted {
+				seller: ALICE,
+				kitty_id: [2u8; 32],
+				start_price: 20,
+				end_price: 10,
+				end: 11,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn nonfungible_traits_work() {
+	use frame::traits::tokens::nonfungible_v2::Inspect as NftInspect;
+	use frame::traits::tokens::nonfungible_v2::Mutate as NftMutate;
+	use frame::traits::tokens::nonfungible_v2::Transfer as NftTransfer;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let kitty_id = [1u8; 32];
+		assert_eq!(<PalletKitties as NftInspect<u64>>::owner(&kitty_id), None);
+		assert!(!<PalletKitties as NftInspect<u64>>::can_transfer(&kitty_id));
+		assert_noop!(
+			<PalletKitties as NftTransfer<u64>>::transfer(&kitty_id, &BOB),
+			Error::<TestRuntime>::NoKitty
+		);
+
+		assert_ok!(<PalletKitties as NftMutate<u64, ()>>::mint_into(&kitty_id, &ALICE, &(), false));
+		assert_noop!(
+			<PalletKitties as NftMutate<u64, ()>>::mint_into(&kitty_id, &BOB, &(), false),
+			Error::<TestRuntime>::DuplicateKitty
+		);
+		assert_eq!(<PalletKitties as NftInspect<u64>>::owner(&kitty_id), Some(ALICE));
+		assert!(<PalletKitties as NftInspect<u64>>::can_transfer(&kitty_id));
+
+		assert_ok!(<PalletKitties as NftTransfer<u64>>::transfer(&kitty_id, &BOB));
+		System::assert_last_event(
+			Event::<TestRuntime>::Transferred { from: ALICE, to: BOB, kitty_id }.into(),
+		);
+		assert_eq!(<PalletKitties as NftInspect<u64>>::owner(&kitty_id), Some(BOB));
+		assert_noop!(
+			<PalletKitties as NftTransfer<u64>>::disable_transfer(&kitty_id),
+			frame::deps::sp_runtime::TokenError::Unsupported
+		);
+
+		assert_noop!(
+			<PalletKitties as NftMutate<u64, ()>>::burn(&kitty_id, Some(&ALICE)),
+			Error::<TestRuntime>::NotOwner
+		);
+		assert_ok!(<PalletKitties as NftMutate<u64, ()>>::burn(&kitty_id, Some(&BOB)));
+		assert_eq!(<PalletKitties as NftInspect<u64>>::owner(&kitty_id), None);
+		// Burning without an owner check is allowed for trusted callers.
+		assert_ok!(<PalletKitties as NftMutate<u64, ()>>::mint_into(
+			&[2u8; 32],
+			&ALICE,
+			&(),
+			false
+		));
+		assert_ok!(<PalletKitties as NftMutate<u64, ()>>::burn(&[2u8; 32], None));
+		assert_eq!(<PalletKitties as NftInspect<u64>>::owner(&[2u8; 32]), None);
+	})
+}
+
+#[test]
+fn kitties_in_auction_cannot_be_transferred_through_nonfungible_traits() {
+	use frame::traits::tokens::nonfungible_v2::Inspect as NftInspect;
+	use frame::traits::tokens::nonfungible_v2::Transfer as NftTransfer;
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 10, 10));
+		assert!(!<PalletKitties as NftInspect<u64>>::can_transfer(&[1u8; 32]));
+		assert_noop!(
+			<PalletKitties as NftTransfer<u64>>::transfer(&[1u8; 32], &BOB),
+			Error::<TestRuntime>::KittyInAuction
+		);
+	})
+}
+
+#[test]
+fn minting_holds_a_deposit() {
+	new_test_ext().execute_with(|| {
+		let reason = HoldReason::KittyStorage.into();
+		KittyDeposit::set(&10);
+		// The minter must be able to pay the deposit.
+		assert_noop!(
+			PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)),
+			frame::deps::sp_runtime::TokenError::FundsUnavailable
+		);
+		assert_ok!(PalletBalances::mint_into(&ALICE, 100));
+		assert_ok!(PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)));
+		let kitty_id = PalletKitties::kitties_of(ALICE)[0];
+		assert_eq!(KittyDeposits::<TestRuntime>::get(kitty_id), Some(10));
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &ALICE), 10);
+		assert_eq!(PalletBalances::balance(&ALICE), 90);
+		// A later change to the deposit does not affect existing kitties.
+		KittyDeposit::set(&20);
+		assert_eq!(KittyDeposits::<TestRuntime>::get(kitty_id), Some(10));
+	})
+}
+
+#[test]
+fn deposits_move_with_the_kitty() {
+	new_test_ext().execute_with(|| {
+		let reason = HoldReason::KittyStorage.into();
+		KittyDeposit::set(&10);
+		assert_ok!(PalletBalances::mint_into(&ALICE, 100));
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		// The deposit can only be held by an account which exists.
+		assert_noop!(
+			PalletKitties::transfer(RuntimeOrigin::signed(ALICE), CHARLIE, [1u8; 32]),
+			frame::deps::sp_runtime::TokenError::CannotCreateHold
+		);
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]));
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &ALICE), 0);
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &BOB), 10);
+		// The deposit is part of what the new owner receives.
+		assert_eq!(PalletBalances::total_balance(&ALICE), 90);
+		assert_eq!(PalletBalances::total_balance(&BOB), 1_010);
+
+		// A sale moves the deposit to the buyer as well, who only pays the price.
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(BOB), [1u8; 32], Some(50)));
+		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(ALICE), [1u8; 32], 50));
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &ALICE), 10);
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &BOB), 0);
+		// Alice is also the creator, so they receive the royalty of the sale.
+		assert_eq!(PalletBalances::total_balance(&ALICE), 55);
+		assert_eq!(PalletBalances::total_balance(&BOB), 1_045);
+	})
+}
+
+#[test]
+fn burning_releases_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let reason = HoldReason::KittyStorage.into();
+		KittyDeposit::set(&10);
+		assert_ok!(PalletBalances::mint_into(&ALICE, 100));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		// Kitties minted without a deposit can still be burned.
+		KittyDeposit::set(&0);
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert!(!KittyDeposits::<TestRuntime>::contains_key([2u8; 32]));
+		assert_ok!(PalletKitties::burn(RuntimeOrigin::signed(ALICE), [2u8; 32]));
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &ALICE), 10);
+
+		assert_ok!(PalletKitties::burn(RuntimeOrigin::signed(ALICE), [1u8; 32]));
+		assert!(!KittyDeposits::<TestRuntime>::contains_key([1u8; 32]));
+		assert_eq!(PalletBalances::balance_on_hold(&reason, &ALICE), 0);
+		assert_eq!(PalletBalances::balance(&ALICE), 100);
+	})
+}
+
+#[test]
+fn set_mint_price_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(MintPrice::<TestRuntime>::get(), 0);
+		// Only the admin origin can set the mint price.
+		assert_noop!(
+			PalletKitties::set_mint_price(RuntimeOrigin::signed(ALICE), 100),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(PalletKitties::set_mint_price(RuntimeOrigin::root(), 100));
+		System::assert_last_event(Event::<TestRuntime>::MintPriceUpdated { price: 100 }.into());
+		assert_eq!(MintPrice::<TestRuntime>::get(), 100);
+	})
+}
+
+#[test]
+fn create_kitty_charges_mint_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletKitties::set_mint_price(RuntimeOrigin::root(), 100));
+		// Cannot create a kitty without the funds to pay for it.
+		assert_noop!(
+			PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)),
+			frame::arithmetic::ArithmeticError::Underflow
+		);
+		// Cannot create a kitty if paying for it would kill your account.
+		assert_ok!(PalletBalances::mint_into(&ALICE, 100));
+		assert_noop!(
+			PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)),
+			frame::deps::sp_runtime::TokenError::NotExpendable
+		);
+		// When everything is right, the price is paid to the beneficiary.
+		assert_ok!(PalletBalances::mint_into(&ALICE, 1));
+		assert_ok!(PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(PalletKitties::kitties_of(ALICE).len(), 1);
+		assert_eq!(PalletBalances::balance(&ALICE), 1);
+		assert_eq!(PalletBalances::balance(&TREASURY), 100);
+		// Kitties are free again once the price is reset.
+		assert_ok!(PalletKitties::set_mint_price(RuntimeOrigin::root(), 0));
+		assert_ok!(PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(PalletBalances::balance(&ALICE), 1);
+	})
+}
+
+#[test]
+fn breeding_does_not_charge_mint_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletKitties::set_mint_price(RuntimeOrigin::root(), 100));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert_ok!(PalletKitties::breed(RuntimeOrigin::signed(ALICE), [1u8; 32], [2u8; 32]));
+		assert_eq!(PalletKitties::kitties_of(ALICE).len(), 3);
+	})
+}
+
+#[test]
+fn force_mint_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			PalletKitties::force_mint(RuntimeOrigin::signed(ALICE), ALICE, [1u8; 32]),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(PalletKitties::force_mint(RuntimeOrigin::root(), BOB, [1u8; 32]));
+		System::assert_has_event(Event::<TestRuntime>::Created { owner: BOB }.into());
+		System::assert_last_event(
+			Event::<TestRuntime>::ForceMinted { owner: BOB, kitty_id: [1u8; 32] }.into(),
+		);
+		assert_eq!(PalletKitties::kitties_of(BOB), vec![[1u8; 32]]);
+		// Forced kitties follow the same rules as any other.
+		assert_noop!(
+			PalletKitties::force_mint(RuntimeOrigin::root(), ALICE, [1u8; 32]),
+			Error::<TestRuntime>::DuplicateKitty
+		);
+	})
+}
+
+#[test]
+fn force_transfer_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_noop!(
+			PalletKitties::force_transfer(RuntimeOrigin::signed(BOB), [1u8; 32], BOB),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			PalletKitties::force_transfer(RuntimeOrigin::root(), [2u8; 32], BOB),
+			Error::<TestRuntime>::NoKitty
+		);
+		assert_ok!(PalletKitties::force_transfer(RuntimeOrigin::root(), [1u8; 32], BOB));
+		System::assert_has_event(
+			Event::<TestRuntime>::Transferred { from: ALICE, to: BOB, kitty_id: [1u8; 32] }.into(),
+		);
+		System::assert_last_event(
+			Event::<TestRuntime>::ForceTransferred { from: ALICE, to: BOB, kitty_id: [1u8; 32] }
+				.into(),
+		);
+		assert_eq!(PalletKitties::kitties_of(BOB), vec![[1u8; 32]]);
+		assert!(PalletKitties::kitties_of(ALICE).is_empty());
+	})
+}
+
+#[test]
+fn force_mint_deposit_is_paid_by_mint_beneficiary() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		KittyDeposit::set(&5);
+		assert_ok!(PalletBalances::mint_into(&TREASURY, 100));
+		assert_ok!(PalletBalances::mint_into(&BOB, 10));
+		assert_ok!(PalletKitties::force_mint(RuntimeOrigin::root(), BOB, [1u8; 32]));
+		// The deposit is held from the owner like any other, but the treasury paid for it.
+		assert_eq!(PalletBalances::balance(&TREASURY), 95);
+		assert_eq!(PalletBalances::balance(&BOB), 10);
+		assert_eq!(PalletBalances::balance_on_hold(&HoldReason::KittyStorage.into(), &BOB), 5);
+		assert_eq!(KittyDeposits::<TestRuntime>::get([1u8; 32]), Some(5));
+		// The owner gets the deposit back when they burn the kitty.
+		assert_ok!(PalletKitties::burn(RuntimeOrigin::signed(BOB), [1u8; 32]));
+		assert_eq!(PalletBalances::balance(&BOB), 15);
+		// Like any kitty with a deposit, a forced kitty can only be given to an existing account.
+		assert_noop!(
+			PalletKitties::force_mint(RuntimeOrigin::root(), CHARLIE, [2u8; 32]),
+			frame::deps::sp_runtime::TokenError::FundsUnavailable
+		);
+	})
+}
+
+#[test]
+fn force_transfer_cancels_auction() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		// The highest bid on a kitty in auction is refunded.
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 10, 10));
+		assert_ok!(PalletKitties::bid(RuntimeOrigin::signed(BOB), [1u8; 32], 100));
+		assert_ok!(PalletKitties::force_transfer(RuntimeOrigin::root(), [1u8; 32], CHARLIE));
+		System::assert_has_event(
+			Event::<TestRuntime>::AuctionCancelled { seller: ALICE, kitty_id: [1u8; 32] }.into(),
+		);
+		assert!(!Auctions::<TestRuntime>::contains_key([1u8; 32]));
+		assert!(!AuctionsEndingAt::<TestRuntime>::contains_key(11));
+		assert_eq!(PalletBalances::balance_on_hold(&HoldReason::AuctionBid.into(), &BOB), 0);
+		assert_eq!(PalletBalances::balance(&BOB), 1_000);
+		assert_eq!(PalletKitties::kitties_of(CHARLIE), vec![[1u8; 32]]);
+	})
+}
+
+#[test]
+fn force_set_price_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_noop!(
+			PalletKitties::force_set_price(RuntimeOrigin::signed(BOB), [1u8; 32], Some(100)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(PalletKitties::force_set_price(RuntimeOrigin::root(), [1u8; 32], Some(100)));
+		System::assert_has_event(
+			Event::<TestRuntime>::PriceSet {
+				owner: ALICE,
+				kitty_id: [1u8; 32],
+				new_price: Some(100),
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::<TestRuntime>::ForcePriceSet {
+				owner: ALICE,
+				kitty_id: [1u8; 32],
+				new_price: Some(100),
+			}
+			.into(),
+		);
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().price, Some(100));
+	})
+}
+
+#[test]
+fn set_paused_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let paused = PauseFlags { mint: true, transfer: false, sale: true };
+		assert_noop!(
+			PalletKitties::set_paused(RuntimeOrigin::signed(ALICE), paused),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(PalletKitties::set_paused(RuntimeOrigin::root(), paused));
+		System::assert_last_event(Event::<TestRuntime>::PausedUpdated { paused }.into());
+		assert_eq!(Paused::<TestRuntime>::get(), paused);
+	})
+}
+
+#[test]
+fn paused_calls_fail() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(ALICE), [2u8; 32], Some(100)));
+		let all = PauseFlags { mint: true, transfer: true, sale: true };
+		assert_ok!(PalletKitties::set_paused(RuntimeOrigin::root(), all));
+
+		assert_noop!(
+			PalletKitties::create_kitty(RuntimeOrigin::signed(ALICE)),
+			Error::<TestRuntime>::MintingPaused
+		);
+		assert_noop!(
+			PalletKitties::breed(RuntimeOrigin::signed(ALICE), [1u8; 32], [2u8; 32]),
+			Error::<TestRuntime>::MintingPaused
+		);
+		assert_noop!(
+			PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]),
+			Error::<TestRuntime>::TransfersPaused
+		);
+		assert_noop!(
+			PalletKitties::set_price(RuntimeOrigin::signed(ALICE), [1u8; 32], Some(100)),
+			Error::<TestRuntime>::SalesPaused
+		);
+		assert_noop!(
+			PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [2u8; 32], 100),
+			Error::<TestRuntime>::SalesPaused
+		);
+		// The admin origin can still act while the pallet is paused.
+		assert_ok!(PalletKitties::force_transfer(RuntimeOrigin::root(), [1u8; 32], BOB));
+
+		// Each flag only pauses its own calls.
+		let sales = PauseFlags { sale: true, ..Default::default() };
+		assert_ok!(PalletKitties::set_paused(RuntimeOrigin::root(), sales));
+		assert_ok!(PalletKitties::transfer(RuntimeOrigin::signed(BOB), ALICE, [1u8; 32]));
+		assert_noop!(
+			PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [2u8; 32], 100),
+			Error::<TestRuntime>::SalesPaused
+		);
+		assert_ok!(PalletKitties::set_paused(RuntimeOrigin::root(), PauseFlags::default()));
+		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [2u8; 32], 100));
+	})
+}
+
+#[test]
+fn paused_sales_postpone_auctions() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [2u8; 32], None));
+		assert_ok!(PalletKitties::mint(ALICE, [3u8; 32], None));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 10, 10));
+		assert_ok!(PalletKitties::bid(RuntimeOrigin::signed(BOB), [1u8; 32], 100));
+		// Block 12 already has as many auctions ending as it can hold.
+		System::set_block_number(2);
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [2u8; 32], 10, 10));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [3u8; 32], 10, 10));
+
+		let sales = PauseFlags { sale: true, ..Default::default() };
+		assert_ok!(PalletKitties::set_paused(RuntimeOrigin::root(), sales));
+		// The auction is not settled, but moved to the next block with room.
+		run_to_block(11);
+		System::assert_last_event(
+			Event::<TestRuntime>::AuctionPostponed { kitty_id: [1u8; 32], end: 13 }.into(),
+		);
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, ALICE);
+		assert_eq!(Auctions::<TestRuntime>::get([1u8; 32]).unwrap().end, 13);
+		assert_eq!(AuctionsEndingAt::<TestRuntime>::get(13).to_vec(), vec![[1u8; 32]]);
+		assert_eq!(PalletBalances::balance_on_hold(&HoldReason::AuctionBid.into(), &BOB), 100);
+
+		// Once sales resume, the auction is settled at its new end.
+		assert_ok!(PalletKitties::set_paused(RuntimeOrigin::root(), PauseFlags::default()));
+		run_to_block(12);
+		run_to_block(13);
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, BOB);
+		assert!(!Auctions::<TestRuntime>::contains_key([1u8; 32]));
+	})
+}
+
+#[test]
+fn paused_transfers_block_nonfungible_transfer() {
+	use frame::traits::tokens::nonfungible_v2::Inspect as NftInspect;
+	use frame::traits::tokens::nonfungible_v2::Transfer as NftTransfer;
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		let transfers = PauseFlags { transfer: true, ..Default::default() };
+		assert_ok!(PalletKitties::set_paused(RuntimeOrigin::root(), transfers));
+		assert!(!<PalletKitties as NftInspect<u64>>::can_transfer(&[1u8; 32]));
+		assert_noop!(
+			<PalletKitties as NftTransfer<u64>>::transfer(&[1u8; 32], &BOB),
+			Error::<TestRuntime>::TransfersPaused
+		);
+		assert_ok!(PalletKitties::set_paused(RuntimeOrigin::root(), PauseFlags::default()));
+		assert_ok!(<PalletKitties as NftTransfer<u64>>::transfer(&[1u8; 32], &BOB));
+		assert_eq!(Kitties::<TestRuntime>::get([1u8; 32]).unwrap().owner, BOB);
+	})
+}
+
+#[test]
+fn pause_filter_works() {
+	use frame::traits::Contains;
+	use frame::traits::Dispatchable;
+
+	new_test_ext().execute_with(|| {
+		let create = RuntimeCall::PalletKitties(Call::create_kitty {});
+		let unpause = RuntimeCall::PalletKitties(Call::set_paused { paused: Default::default() });
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		assert!(PauseFilter::<TestRuntime>::contains(&create));
+
+		let mint = PauseFlags { mint: true, ..Default::default() };
+		assert_ok!(PalletKitties::set_paused(RuntimeOrigin::root(), mint));
+		assert!(!PauseFilter::<TestRuntime>::contains(&create));
+		// Admin calls and the calls of other pallets are never filtered.
+		assert!(PauseFilter::<TestRuntime>::contains(&unpause));
+		assert!(PauseFilter::<TestRuntime>::contains(&remark));
+
+		// The runtime rejects paused calls before they are dispatched.
+		assert_noop!(
+			create.clone().dispatch(RuntimeOrigin::signed(ALICE)),
+			frame_system::Error::<TestRuntime>::CallFiltered
+		);
+		assert_ok!(unpause.dispatch(RuntimeOrigin::root()));
+		assert_ok!(create.dispatch(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(PalletKitties::kitties_of(ALICE).len(), 1);
+	})
+}
+
+#[test]
+fn lock_and_unlock_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		const STAKING: LockIdentifier = *b"staking ";
+		assert_noop!(
+			<PalletKitties as KittyLocker<u64>>::lock(&[1u8; 32], &TREASURY, STAKING),
+			Error::<TestRuntime>::NoKitty
+		);
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_noop!(
+			<PalletKitties as KittyLocker<u64>>::unlock(&[1u8; 32], &TREASURY),
+			Error::<TestRuntime>::NotLocked
+		);
+		assert_ok!(<PalletKitties as KittyLocker<u64>>::lock(&[1u8; 32], &TREASURY, STAKING));
+		System::assert_last_event(
+			Event::<TestRuntime>::Locked {
+				kitty_id: [1u8; 32],
+				lock_owner: TREASURY,
+				reason: STAKING,
+			}
+			.into(),
+		);
+		assert_eq!(
+			<PalletKitties as KittyLocker<u64>>::lock_of(&[1u8; 32]),
+			Some((TREASURY, STAKING))
+		);
+		// A kitty can only hold one lock.
+		assert_noop!(
+			<PalletKitties as KittyLocker<u64>>::lock(&[1u8; 32], &BOB, STAKING),
+			Error::<TestRuntime>::KittyLocked
+		);
+		// Only the account which placed the lock can remove it, not even the owner of the kitty.
+		assert_noop!(
+			<PalletKitties as KittyLocker<u64>>::unlock(&[1u8; 32], &ALICE),
+			Error::<TestRuntime>::NotLockOwner
+		);
+		assert_ok!(<PalletKitties as KittyLocker<u64>>::unlock(&[1u8; 32], &TREASURY));
+		System::assert_last_event(
+			Event::<TestRuntime>::Unlocked { kitty_id: [1u8; 32], lock_owner: TREASURY }.into(),
+		);
+		assert_eq!(<PalletKitties as KittyLocker<u64>>::lock_of(&[1u8; 32]), None);
+	})
+}
+
+#[test]
+fn locked_kitties_cannot_move() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletBalances::mint_into(&BOB, 1_000));
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		// The kitty was listed before it was locked.
+		assert_ok!(PalletKitties::set_price(RuntimeOrigin::signed(ALICE), [1u8; 32], Some(100)));
+		assert_ok!(<PalletKitties as KittyLocker<u64>>::lock(&[1u8; 32], &TREASURY, *b"rental  "));
+
+		assert_noop!(
+			PalletKitties::transfer(RuntimeOrigin::signed(ALICE), BOB, [1u8; 32]),
+			Error::<TestRuntime>::KittyLocked
+		);
+		assert_noop!(
+			PalletKitties::set_price(RuntimeOrigin::signed(ALICE), [1u8; 32], None),
+			Error::<TestRuntime>::KittyLocked
+		);
+		assert_noop!(
+			PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [1u8; 32], 100),
+			Error::<TestRuntime>::KittyLocked
+		);
+		assert_noop!(
+			PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 10, 10),
+			Error::<TestRuntime>::KittyLocked
+		);
+		assert_noop!(
+			PalletKitties::start_dutch_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 20, 10, 10),
+			Error::<TestRuntime>::KittyLocked
+		);
+		assert_noop!(
+			PalletKitties::burn(RuntimeOrigin::signed(ALICE), [1u8; 32]),
+			Error::<TestRuntime>::KittyLocked
+		);
+		// Not even the admin can move a locked kitty, since the lock owner would not know.
+		assert_noop!(
+			PalletKitties::force_transfer(RuntimeOrigin::root(), [1u8; 32], BOB),
+			Error::<TestRuntime>::KittyLocked
+		);
+		assert_eq!(
+			<PalletKitties as KittyLocker<u64>>::lock_of(&[1u8; 32]),
+			Some((TREASURY, *b"rental  "))
+		);
+		assert!(
+			!<PalletKitties as frame::traits::tokens::nonfungible_v2::Inspect<u64>>::can_transfer(
+				&[1u8; 32]
+			)
+		);
+
+		// Once unlocked, the kitty can be bought again.
+		assert_ok!(<PalletKitties as KittyLocker<u64>>::unlock(&[1u8; 32], &TREASURY));
+		assert_ok!(PalletKitties::buy_kitty(RuntimeOrigin::signed(BOB), [1u8; 32], 100));
+		assert_eq!(PalletKitties::kitties_of(BOB), vec![[1u8; 32]]);
+	})
+}
+
+#[test]
+fn kitties_in_auction_cannot_be_locked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PalletKitties::mint(ALICE, [1u8; 32], None));
+		assert_ok!(PalletKitties::start_auction(RuntimeOrigin::signed(ALICE), [1u8; 32], 10, 10));
+		assert_noop!(
+			<PalletKitties as KittyLocker<u64>>::lock(&[1u8; 32], &TREASURY, *b"staking "),
+			Error::<TestRuntime>::KittyInAuction
+		);
+	})
+}
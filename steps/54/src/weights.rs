@@ -0,0 +1,470 @@
+//! Weights for `pallet_kitties`.
+//!
+//! These are placeholder weights. They are written by hand rather than generated by the benchmark
+//! CLI, so their ref times and proof sizes are estimates, not measurements. The `Storage` comments
+//! list the storage items each function accesses in its worst case, as read from the code. Generate
+//! real weights against your own runtime and reference hardware before going to production, for
+//! example with:
+//!
+//! ```sh
+//! frame-omni-bencher v1 benchmark pallet \
+//!     --runtime <path to runtime wasm> \
+//!     --pallet pallet_kitties \
+//!     --extrinsic '*' \
+//!     --steps 50 \
+//!     --repeat 20 \
+//!     --output src/weights.rs \
+//!     --template <path to weight template>
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame::deps::frame_support::weights::constants::RocksDbWeight;
+use frame::prelude::*;
+
+/// Weight functions needed for `pallet_kitties`.
+pub trait WeightInfo {
+	fn create_kitty() -> Weight;
+	fn transfer(n: u32, ) -> Weight;
+	fn set_price() -> Weight;
+	fn buy_kitty() -> Weight;
+	fn breed() -> Weight;
+	fn start_auction() -> Weight;
+	fn bid() -> Weight;
+	fn start_dutch_auction() -> Weight;
+	fn make_offer() -> Weight;
+	fn withdraw_offer() -> Weight;
+	fn accept_offer() -> Weight;
+	fn burn() -> Weight;
+	fn approve_transfer() -> Weight;
+	fn cancel_approval() -> Weight;
+	fn transfer_from() -> Weight;
+	fn set_approval_for_all() -> Weight;
+	fn set_mint_price() -> Weight;
+	fn force_mint() -> Weight;
+	fn force_transfer() -> Weight;
+	fn force_set_price() -> Weight;
+	fn set_paused() -> Weight;
+	fn settle_auctions(n: u32, ) -> Weight;
+	fn expire_offers(n: u32, ) -> Weight;
+}
+
+/// Placeholder weights for `pallet_kitties`, using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `PalletKitties::CountForKitties` (r:1 w:1)
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::CountForKittiesOwned` (r:1 w:1)
+	/// Storage: `PalletKitties::KittiesOwned` (r:0 w:1)
+	/// Storage: `PalletKitties::Creators` (r:0 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `PalletKitties::KittyDeposits` (r:0 w:1)
+	/// Storage: `PalletKitties::MintPrice` (r:1 w:0)
+	/// Storage: `PalletKitties::Paused` (r:1 w:0)
+	fn create_kitty() -> Weight {
+		Weight::from_parts(22_000_000, 3645)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::OperatorApprovals` (r:1 w:0)
+	/// Storage: `PalletKitties::Auctions` (r:1 w:0)
+	/// Storage: `PalletKitties::CountForKittiesOwned` (r:2 w:2)
+	/// Storage: `PalletKitties::KittiesOwned` (r:0 w:2)
+	/// Storage: `PalletKitties::DutchAuctions` (r:0 w:1)
+	/// Storage: `PalletKitties::Approvals` (r:0 w:1)
+	/// Storage: `PalletKitties::KittyDeposits` (r:1 w:0)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `PalletKitties::Paused` (r:1 w:0)
+	/// Storage: `PalletKitties::KittyLocks` (r:1 w:0)
+	/// The range of component `n` is `[0, MaxKittiesOwned - 1]`, and its slope is zero.
+	fn transfer(_n: u32, ) -> Weight {
+		Weight::from_parts(27_000_000, 6038)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::OperatorApprovals` (r:1 w:0)
+	/// Storage: `PalletKitties::Auctions` (r:1 w:0)
+	/// Storage: `PalletKitties::DutchAuctions` (r:0 w:1)
+	/// Storage: `PalletKitties::Paused` (r:1 w:0)
+	/// Storage: `PalletKitties::KittyLocks` (r:1 w:0)
+	fn set_price() -> Weight {
+		Weight::from_parts(16_000_000, 3645)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::DutchAuctions` (r:1 w:1)
+	/// Storage: `PalletKitties::Creators` (r:1 w:0)
+	/// Storage: `System::Account` (r:4 w:4)
+	/// Storage: `PalletKitties::Auctions` (r:1 w:0)
+	/// Storage: `PalletKitties::CountForKittiesOwned` (r:2 w:2)
+	/// Storage: `PalletKitties::KittiesOwned` (r:0 w:2)
+	/// Storage: `PalletKitties::Approvals` (r:0 w:1)
+	/// Storage: `PalletKitties::KittyDeposits` (r:1 w:0)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Storage: `PalletKitties::Paused` (r:1 w:0)
+	/// Storage: `PalletKitties::KittyLocks` (r:1 w:0)
+	fn buy_kitty() -> Weight {
+		Weight::from_parts(101_000_000, 11118)
+			.saturating_add(T::DbWeight::get().reads(15_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:3 w:1)
+	/// Storage: `PalletKitties::BreedingCooldowns` (r:2 w:2)
+	/// Storage: `PalletKitties::CountForKitties` (r:1 w:1)
+	/// Storage: `PalletKitties::CountForKittiesOwned` (r:1 w:1)
+	/// Storage: `PalletKitties::KittiesOwned` (r:0 w:1)
+	/// Storage: `PalletKitties::Creators` (r:0 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `PalletKitties::KittyDeposits` (r:0 w:1)
+	/// Storage: `PalletKitties::Paused` (r:1 w:0)
+	fn breed() -> Weight {
+		Weight::from_parts(43_000_000, 9925)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::Auctions` (r:1 w:1)
+	/// Storage: `PalletKitties::AuctionsEndingAt` (r:1 w:1)
+	/// Storage: `PalletKitties::Paused` (r:1 w:0)
+	/// Storage: `PalletKitties::KittyLocks` (r:1 w:0)
+	/// Storage: `PalletKitties::OperatorApprovals` (r:1 w:0)
+	/// Storage: `PalletKitties::DutchAuctions` (r:0 w:1)
+	fn start_auction() -> Weight {
+		Weight::from_parts(22_000_000, 3645)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `PalletKitties::Auctions` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `PalletKitties::Paused` (r:1 w:0)
+	fn bid() -> Weight {
+		Weight::from_parts(84_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::Auctions` (r:1 w:0)
+	/// Storage: `PalletKitties::DutchAuctions` (r:0 w:1)
+	/// Storage: `PalletKitties::Paused` (r:1 w:0)
+	/// Storage: `PalletKitties::KittyLocks` (r:1 w:0)
+	/// Storage: `PalletKitties::OperatorApprovals` (r:1 w:0)
+	fn start_dutch_auction() -> Weight {
+		Weight::from_parts(17_000_000, 3645)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:0)
+	/// Storage: `PalletKitties::Offers` (r:1 w:1)
+	/// Storage: `PalletKitties::OffersExpiringAt` (r:2 w:2)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `PalletKitties::Paused` (r:1 w:0)
+	fn make_offer() -> Weight {
+		Weight::from_parts(78_000_000, 4764)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: `PalletKitties::Offers` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `PalletKitties::OffersExpiringAt` (r:1 w:1)
+	fn withdraw_offer() -> Weight {
+		Weight::from_parts(52_000_000, 4764)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::Offers` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `PalletKitties::Auctions` (r:1 w:0)
+	/// Storage: `PalletKitties::CountForKittiesOwned` (r:2 w:2)
+	/// Storage: `PalletKitties::KittiesOwned` (r:0 w:2)
+	/// Storage: `PalletKitties::DutchAuctions` (r:0 w:1)
+	/// Storage: `PalletKitties::Approvals` (r:0 w:1)
+	/// Storage: `PalletKitties::KittyDeposits` (r:1 w:0)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `PalletKitties::Paused` (r:1 w:0)
+	/// Storage: `PalletKitties::KittyLocks` (r:1 w:0)
+	/// Storage: `PalletKitties::OffersExpiringAt` (r:1 w:1)
+	fn accept_offer() -> Weight {
+		Weight::from_parts(107_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::Auctions` (r:1 w:0)
+	/// Storage: `PalletKitties::CountForKitties` (r:1 w:1)
+	/// Storage: `PalletKitties::CountForKittiesOwned` (r:1 w:1)
+	/// Storage: `PalletKitties::Offers` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `PalletKitties::KittiesOwned` (r:0 w:1)
+	/// Storage: `PalletKitties::Creators` (r:0 w:1)
+	/// Storage: `PalletKitties::BreedingCooldowns` (r:0 w:1)
+	/// Storage: `PalletKitties::DutchAuctions` (r:0 w:1)
+	/// Storage: `PalletKitties::BurnedKitties` (r:0 w:1)
+	/// Storage: `PalletKitties::Approvals` (r:0 w:1)
+	/// Storage: `PalletKitties::KittyDeposits` (r:1 w:1)
+	/// Storage: `PalletKitties::KittyLocks` (r:1 w:0)
+	fn burn() -> Weight {
+		Weight::from_parts(96_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(16_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:0)
+	/// Storage: `PalletKitties::Approvals` (r:0 w:1)
+	fn approve_transfer() -> Weight {
+		Weight::from_parts(15_000_000, 3645)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:0)
+	/// Storage: `PalletKitties::Approvals` (r:1 w:1)
+	fn cancel_approval() -> Weight {
+		Weight::from_parts(17_000_000, 3645)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::Approvals` (r:1 w:1)
+	/// Storage: `PalletKitties::Auctions` (r:1 w:0)
+	/// Storage: `PalletKitties::CountForKittiesOwned` (r:2 w:2)
+	/// Storage: `PalletKitties::KittiesOwned` (r:0 w:2)
+	/// Storage: `PalletKitties::DutchAuctions` (r:0 w:1)
+	/// Storage: `PalletKitties::KittyDeposits` (r:1 w:0)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `PalletKitties::Paused` (r:1 w:0)
+	/// Storage: `PalletKitties::KittyLocks` (r:1 w:0)
+	fn transfer_from() -> Weight {
+		Weight::from_parts(31_000_000, 6038)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	/// Storage: `PalletKitties::OperatorApprovals` (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PalletKitties::MintPrice` (r:0 w:1)
+	fn set_mint_price() -> Weight {
+		Weight::from_parts(7_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PalletKitties::CountForKitties` (r:1 w:1)
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::BurnedKitties` (r:1 w:0)
+	/// Storage: `PalletKitties::CountForKittiesOwned` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `PalletKitties::KittyDeposits` (r:0 w:1)
+	/// Storage: `PalletKitties::KittiesOwned` (r:0 w:1)
+	/// Storage: `PalletKitties::Creators` (r:0 w:1)
+	fn force_mint() -> Weight {
+		Weight::from_parts(24_000_000, 3645)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::Auctions` (r:1 w:1)
+	/// Storage: `PalletKitties::AuctionsEndingAt` (r:1 w:1)
+	/// Storage: `PalletKitties::CountForKittiesOwned` (r:2 w:2)
+	/// Storage: `PalletKitties::KittyDeposits` (r:1 w:0)
+	/// Storage: `Balances::Holds` (r:3 w:3)
+	/// Storage: `System::Account` (r:3 w:3)
+	/// Storage: `PalletKitties::KittiesOwned` (r:0 w:2)
+	/// Storage: `PalletKitties::DutchAuctions` (r:0 w:1)
+	/// Storage: `PalletKitties::Approvals` (r:0 w:1)
+	/// Storage: `PalletKitties::KittyLocks` (r:1 w:0)
+	fn force_transfer() -> Weight {
+		Weight::from_parts(27_000_000, 6038)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(15_u64))
+	}
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::Auctions` (r:1 w:0)
+	/// Storage: `PalletKitties::DutchAuctions` (r:0 w:1)
+	/// Storage: `PalletKitties::KittyLocks` (r:1 w:0)
+	fn force_set_price() -> Weight {
+		Weight::from_parts(16_000_000, 3645)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `PalletKitties::Paused` (r:0 w:1)
+	fn set_paused() -> Weight {
+		Weight::from_parts(7_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PalletKitties::AuctionsEndingAt` (r:1 w:1)
+	/// Storage: `PalletKitties::Paused` (r:1 w:0)
+	/// Storage: `PalletKitties::Auctions` (r:1 w:1)
+	/// Storage: `PalletKitties::Creators` (r:1 w:0)
+	/// Storage: `PalletKitties::Kitties` (r:1 w:1)
+	/// Storage: `PalletKitties::KittyLocks` (r:1 w:0)
+	/// Storage: `PalletKitties::CountForKittiesOwned` (r:2 w:2)
+	/// Storage: `PalletKitties::KittiesOwned` (r:0 w:2)
+	/// Storage: `PalletKitties::DutchAuctions` (r:0 w:1)
+	/// Storage: `PalletKitties::Approvals` (r:0 w:1)
+	/// Storage: `PalletKitties::KittyDeposits` (r:1 w:0)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Storage: `System::Account` (r:4 w:4)
+	/// The component `n` is the number of auctions which end in the block.
+	fn settle_auctions(n: u32, ) -> Weight {
+		Weight::from_parts(3_000_000, 3506)
+			.saturating_add(Weight::from_parts(110_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((13_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((14_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 11118).saturating_mul(n.into()))
+	}
+	/// Storage: `PalletKitties::OffersExpiringAt` (r:1 w:1)
+	/// Storage: `PalletKitties::Offers` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// The component `n` is the number of offers which expire in the block.
+	fn expire_offers(n: u32, ) -> Weight {
+		Weight::from_parts(3_000_000, 3506)
+			.saturating_add(Weight::from_parts(45_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 4764).saturating_mul(n.into()))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn create_kitty() -> Weight {
+		Weight::from_parts(22_000_000, 3645)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	fn transfer(_n: u32, ) -> Weight {
+		Weight::from_parts(27_000_000, 6038)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	fn set_price() -> Weight {
+		Weight::from_parts(16_000_000, 3645)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn buy_kitty() -> Weight {
+		Weight::from_parts(101_000_000, 11118)
+			.saturating_add(RocksDbWeight::get().reads(15_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+	}
+	fn breed() -> Weight {
+		Weight::from_parts(43_000_000, 9925)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	fn start_auction() -> Weight {
+		Weight::from_parts(22_000_000, 3645)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn bid() -> Weight {
+		Weight::from_parts(84_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn start_dutch_auction() -> Weight {
+		Weight::from_parts(17_000_000, 3645)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn make_offer() -> Weight {
+		Weight::from_parts(78_000_000, 4764)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	fn withdraw_offer() -> Weight {
+		Weight::from_parts(52_000_000, 4764)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn accept_offer() -> Weight {
+		Weight::from_parts(107_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+	}
+	fn burn() -> Weight {
+		Weight::from_parts(96_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(16_u64))
+	}
+	fn approve_transfer() -> Weight {
+		Weight::from_parts(15_000_000, 3645)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn cancel_approval() -> Weight {
+		Weight::from_parts(17_000_000, 3645)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn transfer_from() -> Weight {
+		Weight::from_parts(31_000_000, 6038)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	fn set_approval_for_all() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_mint_price() -> Weight {
+		Weight::from_parts(7_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn force_mint() -> Weight {
+		Weight::from_parts(24_000_000, 3645)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	fn force_transfer() -> Weight {
+		Weight::from_parts(27_000_000, 6038)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+	}
+	fn force_set_price() -> Weight {
+		Weight::from_parts(16_000_000, 3645)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn set_paused() -> Weight {
+		Weight::from_parts(7_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn settle_auctions(n: u32, ) -> Weight {
+		Weight::from_parts(3_000_000, 3506)
+			.saturating_add(Weight::from_parts(110_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((13_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((14_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 11118).saturating_mul(n.into()))
+	}
+	fn expire_offers(n: u32, ) -> Weight {
+		Weight::from_parts(3_000_000, 3506)
+			.saturating_add(Weight::from_parts(45_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 4764).saturating_mul(n.into()))
+	}
+}